@@ -0,0 +1,69 @@
+use std::{env, fs, path::Path};
+
+// Every `src/programs/dayNN.rs` module is registered automatically: it gets a
+// `mod` declaration, an entry in `PROGRAMS` for its `DayNN` solver and a test
+// checking its recorded answers in `data/dayNN/answers.txt`.
+fn main() {
+    let programs_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/programs");
+    println!("cargo:rerun-if-changed={}", programs_dir.display());
+
+    let mut modules = fs::read_dir(&programs_dir)
+        .expect("Failed to read src/programs")
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            let module = file_name.strip_suffix(".rs")?.to_owned();
+            let digits = module.strip_prefix("day")?;
+            if digits.len() == 2 && digits.chars().all(|c| c.is_ascii_digit()) {
+                Some(module)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<String>>();
+    modules.sort();
+
+    let mut registry = String::new();
+    for module in &modules {
+        registry.push_str(&format!(
+            "#[path = {:?}]\npub mod {};\n",
+            programs_dir
+                .join(format!("{}.rs", module))
+                .display()
+                .to_string(),
+            module
+        ));
+    }
+    registry.push_str("\npub static PROGRAMS: &[Program] = &[\n");
+    for module in &modules {
+        registry.push_str(&format!(
+            "    Program::of::<{}::{}>(),\n",
+            module,
+            solver_name(module)
+        ));
+    }
+    registry.push_str("];\n");
+
+    let mut answer_tests = String::new();
+    for module in &modules {
+        answer_tests.push_str(&format!(
+            "#[test]\nfn {}() {{\n    crate::verify::assert_verified(&Program::of::<{}::{}>());\n}}\n",
+            module,
+            module,
+            solver_name(module)
+        ));
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("programs.rs"), registry)
+        .expect("Failed to write program registry");
+    fs::write(Path::new(&out_dir).join("answer_tests.rs"), answer_tests)
+        .expect("Failed to write answer tests");
+}
+
+fn solver_name(module: &str) -> String {
+    let mut chars = module.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// A solved part's result, kept typed so library callers do not have to re-parse it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    Text(String),
+    Grid(Vec<String>),
+    None,
+}
+
+impl Answer {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::BigInteger(_) => "big_integer",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+            Answer::None => "none",
+        }
+    }
+
+    /// Renders the answer as a single JSON object, e.g. `{"kind":"integer","value":42}`.
+    /// Big integers are written as strings so consumers do not lose precision.
+    pub fn to_json(&self) -> String {
+        let value = match self {
+            Answer::Integer(value) => format!(",\"value\":{}", value),
+            Answer::BigInteger(value) => format!(",\"value\":\"{}\"", value),
+            Answer::Text(value) => format!(",\"value\":{}", json_string(value)),
+            Answer::Grid(rows) => {
+                let rows = rows.iter().map(|row| json_string(row)).collect::<Vec<_>>();
+                format!(",\"rows\":[{}]", rows.join(","))
+            }
+            Answer::None => String::new(),
+        };
+        format!("{{\"kind\":\"{}\"{}}}", self.kind(), value)
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigInteger(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::None => write!(f, "no answer"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::BigInteger(value as i128),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::BigInteger(value.into()),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        Answer::BigInteger(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::None, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(24000).to_string(), "24000");
+        assert_eq!(Answer::from(String::from("CMZ")).to_string(), "CMZ");
+        assert_eq!(
+            Answer::Grid(vec![String::from("#."), String::from(".#")]).to_string(),
+            "#.\n.#"
+        );
+        assert_eq!(Answer::from(None::<usize>).to_string(), "no answer");
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            Answer::from(7).to_json(),
+            "{\"kind\":\"integer\",\"value\":7}"
+        );
+        assert_eq!(
+            Answer::from(u64::MAX).to_json(),
+            "{\"kind\":\"big_integer\",\"value\":\"18446744073709551615\"}"
+        );
+        assert_eq!(
+            Answer::from(String::from("a\"b")).to_json(),
+            "{\"kind\":\"text\",\"value\":\"a\\\"b\"}"
+        );
+        assert_eq!(
+            Answer::Grid(vec![String::from("#."), String::from(".#")]).to_json(),
+            "{\"kind\":\"grid\",\"rows\":[\"#.\",\".#\"]}"
+        );
+        assert_eq!(Answer::None.to_json(), "{\"kind\":\"none\"}");
+    }
+}
@@ -0,0 +1,410 @@
+use crate::error::{Error, Result};
+use crate::parts::{Params, Part};
+use crate::programs::parse_program_name;
+use crate::solver::Program;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+
+pub const USAGE: &str = "\
+Usage:
+  adventofcode2022 run <day> [--part 1|2|both|<variant>] [--<option> <value>]...
+                       [--input <path>|-] [--stream] [--json] [--time]
+  adventofcode2022 all
+  adventofcode2022 bench [<day>] [--runs <n>] [--input <path>|-]
+  adventofcode2022 verify [<day>]
+  adventofcode2022 list
+  adventofcode2022 <program> <input>    e.g. 01a data/day01/input.txt
+  adventofcode2022 --help
+
+Commands:
+  run     Solve a day's puzzle; <day> is a number such as 1, 01 or day01
+  all     Solve both parts of every registered day and print a summary table
+  bench   Repeat a day's solver (or every day's) and report min/median/max timings
+  verify  Compare answers with those recorded in data/dayNN/answers.txt
+  list    Show every registered day with the variants and options it accepts
+
+Options:
+  --part <which>      Which part to solve: 1, 2, both or a variant the day offers
+                      (default: both, or the variants named by other options)
+  --<option> <value>  Set a day's option, e.g. `run 1 --top 5` or `run 6 --marker-length 20`;
+                      options named after a variant select it
+  --input <path|->    Puzzle input file, or - for stdin (default: data/dayNN/input.txt)
+  --stream            Solve while reading instead of loading the whole input first;
+                      days 01 and 06 stream incrementally, others read everything
+  --json              Print answers as JSON objects, one per line
+  --time              Report how long reading, parsing and each part took
+  --runs <n>          How many times bench repeats each solver (default: 10)
+  -h, --help          Show this message";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    All,
+    Bench(BenchOptions),
+    Verify(Option<u8>),
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub day: u8,
+    /// The parts asked for with `--part`, if any; the program resolves the default.
+    pub parts: Option<Vec<Part>>,
+    pub params: Params,
+    pub input: InputSource,
+    pub stream: bool,
+    pub json: bool,
+    pub time: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub day: Option<u8>,
+    pub runs: usize,
+    pub input: InputSource,
+}
+
+pub const DEFAULT_BENCH_RUNS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Default,
+    Path(String),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn describe(&self, program: &Program) -> String {
+        match self {
+            InputSource::Default => program.input_path(),
+            InputSource::Path(path) => path.clone(),
+            InputSource::Stdin => String::from("stdin"),
+        }
+    }
+
+    pub fn read(&self, program: &Program) -> Result<String> {
+        let path = self.describe(program);
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|error| Error::io(&path, error))?;
+                Ok(input)
+            }
+            _ => fs::read_to_string(&path).map_err(|error| Error::io(&path, error)),
+        }
+    }
+
+    pub fn open(&self, program: &Program) -> Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            _ => {
+                let path = self.describe(program);
+                let file = File::open(&path).map_err(|error| Error::io(&path, error))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+        }
+    }
+}
+
+/// Parses the command line, not including the binary name.
+pub fn parse_args(args: &[String]) -> Result<Command> {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Command::Help);
+    }
+    match args.first().map(String::as_str) {
+        None | Some("help") => Ok(Command::Help),
+        Some("all") => match args.len() {
+            1 => Ok(Command::All),
+            _ => Err(unexpected(&args[1])),
+        },
+        Some("list") => match args.len() {
+            1 => Ok(Command::List),
+            _ => Err(unexpected(&args[1])),
+        },
+        Some("run") => parse_run(&args[1..]).map(Command::Run),
+        Some("bench") => parse_bench(&args[1..]).map(Command::Bench),
+        Some("verify") => match &args[1..] {
+            [] => Ok(Command::Verify(None)),
+            [day] => Ok(Command::Verify(Some(parse_day(day)?))),
+            [_, extra, ..] => Err(unexpected(extra)),
+        },
+        Some(program_name) => parse_legacy(program_name, &args[1..]).map(Command::Run),
+    }
+}
+
+fn parse_legacy(program_name: &str, args: &[String]) -> Result<RunOptions> {
+    let (day, part) = parse_program_name(program_name)
+        .ok_or_else(|| Error::Usage(format!("Unknown command {}", program_name)))?;
+    match args {
+        [input] => Ok(RunOptions {
+            day,
+            parts: Some(vec![part]),
+            params: Params::default(),
+            input: parse_input(input),
+            stream: false,
+            json: false,
+            time: false,
+        }),
+        [] => Err(Error::Usage(format!(
+            "Missing input file for {}",
+            program_name
+        ))),
+        [_, extra, ..] => Err(unexpected(extra)),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<RunOptions> {
+    let day = args
+        .first()
+        .filter(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| Error::Usage(String::from("Missing <day> for run")))?;
+    let mut options = RunOptions {
+        day: parse_day(day)?,
+        parts: None,
+        params: Params::default(),
+        input: InputSource::Default,
+        stream: false,
+        json: false,
+        time: false,
+    };
+    for_each_option(&args[1..], |flag, value| {
+        match flag {
+            "--part" => options.parts = Some(parse_parts(&value()?)?),
+            "--input" => options.input = parse_input(&value()?),
+            "--stream" => options.stream = true,
+            "--json" => options.json = true,
+            "--time" => options.time = true,
+            // Anything else is one of the day's own options, checked once the day is known.
+            _ => match flag.strip_prefix("--").filter(|name| !name.is_empty()) {
+                Some(name) => options.params.set(name, &value()?),
+                None => return Err(unexpected(flag)),
+            },
+        }
+        Ok(())
+    })?;
+    Ok(options)
+}
+
+fn parse_bench(args: &[String]) -> Result<BenchOptions> {
+    let day = args.first().filter(|arg| !arg.starts_with("--"));
+    let mut options = BenchOptions {
+        day: day.map(|day| parse_day(day)).transpose()?,
+        runs: DEFAULT_BENCH_RUNS,
+        input: InputSource::Default,
+    };
+    let options_start = if day.is_some() { 1 } else { 0 };
+    for_each_option(&args[options_start..], |flag, value| {
+        match flag {
+            "--runs" => {
+                let runs = value()?;
+                options.runs = runs
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&runs| runs > 0)
+                    .ok_or_else(|| Error::Usage(format!("Invalid run count {}", runs)))?;
+            }
+            "--input" if options.day.is_some() => options.input = parse_input(&value()?),
+            "--input" => return Err(Error::Usage(String::from("--input needs a <day>"))),
+            _ => return Err(unexpected(flag)),
+        }
+        Ok(())
+    })?;
+    Ok(options)
+}
+
+/// Calls `handle` with each `--flag`, and a way to take its value from either
+/// `--flag=value` or the following argument.
+fn for_each_option(
+    args: &[String],
+    mut handle: impl FnMut(&str, &mut dyn FnMut() -> Result<String>) -> Result<()>,
+) -> Result<()> {
+    let mut remaining = args.iter();
+    while let Some(arg) = remaining.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_owned())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| remaining.next().cloned())
+                .ok_or_else(|| Error::Usage(format!("Missing value for {}", flag)))
+        };
+        handle(flag, &mut value)?;
+    }
+    Ok(())
+}
+
+pub fn parse_day(arg: &str) -> Result<u8> {
+    let digits = arg.strip_prefix("day").unwrap_or(arg);
+    digits
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| Error::Usage(format!("Invalid day {}, expected 1 to 25", arg)))
+}
+
+fn parse_parts(arg: &str) -> Result<Vec<Part>> {
+    match arg {
+        "both" => Ok(vec![Part::One, Part::Two]),
+        "1" | "2" => Ok(vec![Part::from_label(arg)]),
+        _ if arg.starts_with(|c: char| c.is_ascii_lowercase()) => Ok(vec![Part::from_label(arg)]),
+        _ => Err(Error::Usage(format!(
+            "Invalid part {}, expected 1, 2, both or a variant",
+            arg
+        ))),
+    }
+}
+
+fn parse_input(arg: &str) -> InputSource {
+    match arg {
+        "-" => InputSource::Stdin,
+        path => InputSource::Path(String::from(path)),
+    }
+}
+
+fn unexpected(arg: &str) -> Error {
+    Error::Usage(format!("Unexpected argument {}", arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse_args(&args("run 4 --part 2 --input - --stream")),
+            Ok(Command::Run(RunOptions {
+                day: 4,
+                parts: Some(vec![Part::Two]),
+                params: Params::default(),
+                input: InputSource::Stdin,
+                stream: true,
+                json: false,
+                time: false,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("run day06 --json --time --input=data/day06/test.txt")),
+            Ok(Command::Run(RunOptions {
+                day: 6,
+                parts: None,
+                params: Params::default(),
+                input: InputSource::Path(String::from("data/day06/test.txt")),
+                stream: false,
+                json: true,
+                time: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_run_day_options() {
+        assert_eq!(
+            parse_args(&args("run 1 --top 5 --part=both")),
+            Ok(Command::Run(RunOptions {
+                day: 1,
+                parts: Some(vec![Part::One, Part::Two]),
+                params: Params::default().with("top", "5"),
+                input: InputSource::Default,
+                stream: false,
+                json: false,
+                time: false,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("run 6 --marker-length=20 --part marker-length")),
+            Ok(Command::Run(RunOptions {
+                day: 6,
+                parts: Some(vec![Part::Variant(String::from("marker-length"))]),
+                params: Params::default().with("marker-length", "20"),
+                input: InputSource::Default,
+                stream: false,
+                json: false,
+                time: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse_args(&args("bench")),
+            Ok(Command::Bench(BenchOptions {
+                day: None,
+                runs: DEFAULT_BENCH_RUNS,
+                input: InputSource::Default,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("bench 3 --runs 50 --input data/day03/test.txt")),
+            Ok(Command::Bench(BenchOptions {
+                day: Some(3),
+                runs: 50,
+                input: InputSource::Path(String::from("data/day03/test.txt")),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_program_name() {
+        assert_eq!(
+            parse_args(&args("01b data/day01/input.txt")),
+            Ok(Command::Run(RunOptions {
+                day: 1,
+                parts: Some(vec![Part::Two]),
+                params: Params::default(),
+                input: InputSource::Path(String::from("data/day01/input.txt")),
+                stream: false,
+                json: false,
+                time: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_commands() {
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+        assert_eq!(parse_args(&args("run 1 --help")), Ok(Command::Help));
+        assert_eq!(parse_args(&args("list")), Ok(Command::List));
+        assert_eq!(parse_args(&args("all")), Ok(Command::All));
+        assert_eq!(parse_args(&args("verify")), Ok(Command::Verify(None)));
+        assert_eq!(parse_args(&args("verify 04")), Ok(Command::Verify(Some(4))));
+    }
+
+    #[test]
+    fn test_parse_args_rejects_bad_usage() {
+        let cases = [
+            ("run", "Missing <day> for run"),
+            ("run 26", "Invalid day 26, expected 1 to 25"),
+            (
+                "run 1 --part 3",
+                "Invalid part 3, expected 1, 2, both or a variant",
+            ),
+            ("run 1 --input", "Missing value for --input"),
+            ("run 1 --top", "Missing value for --top"),
+            ("run 1 verbose", "Unexpected argument verbose"),
+            ("bench --runs 0", "Invalid run count 0"),
+            (
+                "bench --input data/day01/input.txt",
+                "--input needs a <day>",
+            ),
+            ("01a", "Missing input file for 01a"),
+            ("frobnicate", "Unknown command frobnicate"),
+        ];
+        cases.iter().for_each(|(line, message)| {
+            assert_eq!(
+                parse_args(&args(line)),
+                Err(Error::Usage(String::from(*message)))
+            );
+        });
+    }
+}
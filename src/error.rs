@@ -0,0 +1,93 @@
+use std::{fmt, io};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    UnknownProgram(String),
+    Usage(String),
+    Io { path: String, message: String },
+    Failed(String),
+}
+
+/// Where and why a day's input could not be understood.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl Error {
+    /// Builds a parse error; the day is filled in by the solver that reports it.
+    pub fn parse(line: usize, column: usize, text: &str, message: &str) -> Self {
+        Error::Parse(ParseError {
+            day: 0,
+            line,
+            column,
+            text: String::from(text),
+            message: String::from(message),
+        })
+    }
+
+    pub fn io(path: &str, error: io::Error) -> Self {
+        Error::Io {
+            path: String::from(path),
+            message: error.to_string(),
+        }
+    }
+
+    pub fn for_day(self, day: u8) -> Self {
+        match self {
+            Error::Parse(error) => Error::Parse(ParseError { day, ..error }),
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::UnknownProgram(name) => write!(f, "Unknown program {}", name),
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Io { path, message } => write!(f, "Failed to read {}: {}", path, message),
+            Error::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: {}, found {:?}",
+            self.day, self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_display() {
+        let error = Error::parse(3, 5, "7-x", "expected a section number").for_day(4);
+        assert_eq!(
+            error.to_string(),
+            "day 04, line 3, column 5: expected a section number, found \"7-x\""
+        );
+    }
+
+    #[test]
+    fn test_for_day_keeps_other_errors() {
+        let error = Error::UnknownProgram(String::from("99a")).for_day(4);
+        assert_eq!(error, Error::UnknownProgram(String::from("99a")));
+    }
+}
@@ -1,7 +1,19 @@
+pub mod answer;
+pub mod cli;
+pub mod error;
+pub mod parsing;
 pub mod parts;
 pub mod programs;
+pub mod solver;
+pub mod summary;
+
+pub mod timing;
+pub mod verify;
 
 // TODO: Figure out how to not need this public
 pub mod test_helpers;
 
+pub use answer::Answer;
+pub use error::{Error, ParseError, Result};
 pub use programs::*;
+pub use solver::{Program, Solver};
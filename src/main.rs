@@ -1,13 +1,203 @@
-use adventofcode2022::programs;
-use std::{env, fs};
+use adventofcode2022::cli::{self, BenchOptions, Command, InputSource, RunOptions};
+use adventofcode2022::parts::{Part, Selector};
+use adventofcode2022::summary::{self, Outcome, Row};
+use adventofcode2022::timing::{self, format_duration, timed};
+use adventofcode2022::verify::{self, Status};
+use adventofcode2022::{programs, Error, Program, Result};
+use std::{env, fs, process, time::Duration};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    let program = &args[1];
-    let input_file_path = &args[2];
+    let exit_code = match cli::parse_args(&args).and_then(execute) {
+        Ok(()) => 0,
+        Err(error @ Error::Usage(_)) => {
+            eprintln!("{}\n\n{}", error, cli::USAGE);
+            2
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            1
+        }
+    };
+    process::exit(exit_code);
+}
+
+fn execute(command: Command) -> Result<()> {
+    match command {
+        Command::Run(options) => run(options),
+        Command::All => run_all(),
+        Command::Bench(options) => bench(options),
+        Command::Verify(day) => verify(day),
+        Command::List => {
+            programs::PROGRAMS.iter().for_each(|program| {
+                println!("{:02}  {}", program.day, program.title);
+                let variants = program.variants.iter().map(|spec| (spec, "variant"));
+                let params = program.params.iter().map(|spec| (spec, "option"));
+                variants.chain(params).for_each(|(spec, kind)| {
+                    let flag = format!("--{} <n>", spec.name);
+                    println!("    {:<22} {:<8} {}", flag, kind, spec.about)
+                });
+            });
+            Ok(())
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn find_program(day: u8) -> Result<&'static Program> {
+    programs::find_program(day).ok_or_else(|| Error::UnknownProgram(format!("day {:02}", day)))
+}
+
+fn run(options: RunOptions) -> Result<()> {
+    let program = find_program(options.day)?;
+    let selectors = program.selectors(options.parts, options.params)?;
+    let (answers, phases) = if options.stream {
+        let mut reader = options.input.open(program)?;
+        let (answers, elapsed) = timed(|| program.stream(&mut reader, &selectors));
+        (answers?, vec![(String::from("stream"), elapsed)])
+    } else {
+        let (input, read_time) = timed(|| options.input.read(program));
+        let execution = program.execute(&selectors, &input?)?;
+        let phases = [("read", read_time), ("parse", execution.parse_time)]
+            .into_iter()
+            .map(|(phase, elapsed)| (String::from(phase), elapsed))
+            .chain(execution.parts.iter().map(|part| {
+                let phase = format!("part {}", part.selector.part);
+                (phase, part.elapsed)
+            }))
+            .collect::<Vec<_>>();
+        let answers = execution
+            .parts
+            .into_iter()
+            .map(|part| part.answer)
+            .collect();
+        (answers, phases)
+    };
+
+    for (selector, answer) in selectors.iter().zip(&answers) {
+        let part = &selector.part;
+        if options.json {
+            let part = match part {
+                Part::Variant(name) => format!("\"{}\"", name),
+                _ => part.to_string(),
+            };
+            println!(
+                "{{\"day\":{},\"part\":{},\"answer\":{}}}",
+                program.day,
+                part,
+                answer.to_json()
+            );
+        } else if selectors.len() == 1 {
+            println!("{}", answer);
+        } else if answer.to_string().contains('\n') {
+            println!("Part {}:\n{}", part, answer);
+        } else {
+            println!("Part {}: {}", part, answer);
+        }
+    }
 
-    let input = fs::read_to_string(input_file_path).expect("Failed to read input file");
+    if options.time {
+        if options.json {
+            let timings = phases
+                .iter()
+                .map(|(phase, elapsed)| {
+                    format!("\"{}\":{}", phase.replace(' ', "_"), elapsed.as_nanos())
+                })
+                .collect::<Vec<String>>();
+            println!(
+                "{{\"day\":{},\"timing_ns\":{{{}}}}}",
+                program.day,
+                timings.join(",")
+            );
+        } else {
+            println!();
+            phases.iter().for_each(|(phase, elapsed)| {
+                println!("{:<7} {}", phase, format_duration(*elapsed))
+            });
+        }
+    }
+    Ok(())
+}
+
+fn bench(options: BenchOptions) -> Result<()> {
+    let benchmarks = match options.day {
+        Some(day) => {
+            let program = find_program(day)?;
+            let input = options.input.read(program)?;
+            vec![timing::bench(program, &input, options.runs)?]
+        }
+        None => programs::PROGRAMS
+            .iter()
+            .filter_map(|program| {
+                let input = InputSource::Default.read(program).ok()?;
+                Some(timing::bench(program, &input, options.runs))
+            })
+            .collect::<Result<Vec<_>>>()?,
+    };
+    print!("{}", timing::render_benchmarks(&benchmarks));
+    Ok(())
+}
+
+fn run_all() -> Result<()> {
+    let mut rows: Vec<Row> = vec![];
+    for program in programs::PROGRAMS {
+        let path = program.input_path();
+        let input = fs::read_to_string(&path).ok();
+        for (part, which) in [(1, Part::One), (2, Part::Two)] {
+            let selector = Selector::from(which);
+            let (outcome, elapsed) = match &input {
+                Some(input) => {
+                    let (result, elapsed) = timed(|| program.run(&selector, input));
+                    match result {
+                        Ok(answer) => (Outcome::Solved(answer), elapsed),
+                        Err(error) => (Outcome::Failed(error), elapsed),
+                    }
+                }
+                None => (Outcome::MissingInput(path.clone()), Duration::ZERO),
+            };
+            rows.push(Row {
+                day: program.day,
+                part,
+                outcome,
+                elapsed,
+            });
+        }
+    }
+
+    print!("{}", summary::render_table(&rows));
+    let failures = rows
+        .iter()
+        .filter(|row| matches!(row.outcome, Outcome::Failed(_)))
+        .count();
+    match failures {
+        0 => Ok(()),
+        _ => Err(Error::Failed(format!("{} part(s) failed", failures))),
+    }
+}
 
-    println!("{}", programs::run_program(program, &input));
+fn verify(day: Option<u8>) -> Result<()> {
+    let programs = match day {
+        Some(day) => vec![find_program(day)?],
+        None => programs::PROGRAMS.iter().collect(),
+    };
+    let checks = programs
+        .into_iter()
+        .flat_map(verify::verify_program)
+        .collect::<Vec<_>>();
+    print!("{}", verify::render_checks(&checks));
+    let failures = checks
+        .iter()
+        .filter(|check| matches!(check.status, Status::Fail { .. } | Status::Error(_)))
+        .count();
+    match failures {
+        0 => Ok(()),
+        _ => Err(Error::Failed(format!(
+            "{} answer(s) did not match",
+            failures
+        ))),
+    }
 }
@@ -0,0 +1,57 @@
+use crate::error::{Error, Result};
+use std::str::FromStr;
+
+/// Non-blank lines of the input, paired with their 1-based line numbers.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim_end()))
+        .filter(|(_, line)| !line.trim().is_empty())
+}
+
+/// 1-based column at which `field` starts, when `field` is a slice of `line`.
+pub fn column_of(line: &str, field: &str) -> usize {
+    let offset = (field.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() {
+        line[..offset].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+/// Parses `field`, a slice of `line`, reporting its position if it is malformed.
+pub fn parse_field<T: FromStr>(
+    line_number: usize,
+    line: &str,
+    field: &str,
+    expected: &str,
+) -> Result<T> {
+    field
+        .trim()
+        .parse::<T>()
+        .map_err(|_| Error::parse(line_number, column_of(line, field), field, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbered_lines_skips_blank_lines() {
+        let lines = numbered_lines("a\n\nb \n").collect::<Vec<(usize, &str)>>();
+        assert_eq!(lines, vec![(1, "a"), (3, "b")]);
+    }
+
+    #[test]
+    fn test_parse_field_reports_column() {
+        let line = "2-4,6-x";
+        let field = &line[6..];
+        let error = parse_field::<i32>(7, line, field, "expected a number").unwrap_err();
+        assert_eq!(error, Error::parse(7, 7, "x", "expected a number"));
+        assert_eq!(
+            parse_field::<i32>(7, line, &line[2..3], "expected a number"),
+            Ok(4)
+        );
+    }
+}
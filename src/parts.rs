@@ -1,4 +1,164 @@
-pub enum Parts {
+use crate::error::{Error, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Which answer to compute: one of the puzzle's two parts, or a named variant that
+/// a day offers on top of them (such as day 1's `top`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Part {
     One,
     Two,
+    Variant(String),
+}
+
+impl Part {
+    pub fn from_label(label: &str) -> Self {
+        match label {
+            "1" => Part::One,
+            "2" => Part::Two,
+            name => Part::Variant(String::from(name)),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Part::One => "1",
+            Part::Two => "2",
+            Part::Variant(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Named values that tune how a day solves a part, e.g. `top=5` or `row=10`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(String::from(name), String::from(value));
+    }
+
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The parameter's value, or `default` when it was not given.
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T> {
+        match self.values.get(name) {
+            Some(value) => parse_value(name, value),
+            None => Ok(default),
+        }
+    }
+
+    pub fn require<T: FromStr>(&self, name: &str) -> Result<T> {
+        match self.values.get(name) {
+            Some(value) => parse_value(name, value),
+            None => Err(Error::Usage(format!("Missing value for --{}", name))),
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value
+        .parse::<T>()
+        .map_err(|_| Error::Usage(format!("Invalid value {} for --{}", value, name)))
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<String>>();
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+/// A part together with the parameters to solve it with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector {
+    pub part: Part,
+    pub params: Params,
+}
+
+impl Selector {
+    pub fn new(part: Part, params: Params) -> Self {
+        Selector { part, params }
+    }
+}
+
+impl From<Part> for Selector {
+    fn from(part: Part) -> Self {
+        Selector::new(part, Params::default())
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.params.is_empty() {
+            true => write!(f, "{}", self.part),
+            false => write!(f, "{} {}", self.part, self.params),
+        }
+    }
+}
+
+/// Describes a variant or parameter a day accepts, for validation and `list`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptionSpec {
+    pub name: &'static str,
+    pub about: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_labels() {
+        assert_eq!(Part::from_label("1"), Part::One);
+        assert_eq!(Part::from_label("2"), Part::Two);
+        assert_eq!(Part::from_label("top"), Part::Variant(String::from("top")));
+        assert_eq!(Part::Variant(String::from("top")).label(), "top");
+    }
+
+    #[test]
+    fn test_params_get() {
+        let params = Params::default().with("top", "5").with("row", "x");
+        assert_eq!(params.get_or("top", 3usize), Ok(5));
+        assert_eq!(params.get_or("knots", 2usize), Ok(2));
+        assert_eq!(
+            params.get_or("row", 10i64),
+            Err(Error::Usage(String::from("Invalid value x for --row")))
+        );
+        assert_eq!(
+            params.require::<usize>("knots"),
+            Err(Error::Usage(String::from("Missing value for --knots")))
+        );
+    }
+
+    #[test]
+    fn test_selector_display() {
+        let selector = Selector::new(Part::One, Params::default().with("row", "10"));
+        assert_eq!(selector.to_string(), "1 row=10");
+        assert_eq!(Selector::from(Part::Two).to_string(), "2");
+    }
 }
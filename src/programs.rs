@@ -1,23 +1,101 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day06;
-
-use crate::parts::Parts;
-
-pub fn run_program(program_name: &str, input: &str) -> String {
-    match program_name {
-        "01a" | "1a" => day01::run(Parts::One, &input).to_owned(),
-        "01b" | "1b" => day01::run(Parts::Two, &input).to_owned(),
-        "02a" | "2a" => day02::run(Parts::One, &input).to_owned(),
-        "02b" | "2b" => day02::run(Parts::Two, &input).to_owned(),
-        "03a" | "3a" => day03::run(Parts::One, &input).to_owned(),
-        "03b" | "3b" => day03::run(Parts::Two, &input).to_owned(),
-        "04a" | "4a" => day04::run(Parts::One, &input).to_owned(),
-        "04b" | "4b" => day04::run(Parts::Two, &input).to_owned(),
-        "06a" | "6a" => day06::run(Parts::One, &input).to_owned(),
-        "06b" | "6b" => day06::run(Parts::Two, &input).to_owned(),
-        _ => String::from("Unknown program"),
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parts::{Part, Selector};
+use crate::solver::Program;
+
+// Generated by build.rs from the `dayNN.rs` files in `src/programs`.
+include!(concat!(env!("OUT_DIR"), "/programs.rs"));
+
+pub fn find_program(day: u8) -> Option<&'static Program> {
+    PROGRAMS.iter().find(|program| program.day == day)
+}
+
+/// Splits a program name such as `01a` or `6b` into its day and part.
+pub fn parse_program_name(program_name: &str) -> Option<(u8, Part)> {
+    let (day, part) = if let Some(day) = program_name.strip_suffix('a') {
+        (day, Part::One)
+    } else {
+        (program_name.strip_suffix('b')?, Part::Two)
+    };
+    Some((day.parse::<u8>().ok()?, part))
+}
+
+pub fn run_program(program_name: &str, input: &str) -> Result<Answer> {
+    let unknown = || Error::UnknownProgram(String::from(program_name));
+    let (day, part) = parse_program_name(program_name).ok_or_else(unknown)?;
+    find_program(day)
+        .ok_or_else(unknown)?
+        .run(&Selector::from(part), input)
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+
+    // One test per registered day, generated by build.rs.
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parts::Params;
+
+    #[test]
+    fn test_programs_are_registered_in_order() {
+        let days = PROGRAMS.iter().map(|p| p.day).collect::<Vec<u8>>();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert!(!days.is_empty());
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn test_parse_program_name() {
+        assert!(matches!(parse_program_name("01a"), Some((1, Part::One))));
+        assert!(matches!(parse_program_name("1b"), Some((1, Part::Two))));
+        assert!(matches!(parse_program_name("06b"), Some((6, Part::Two))));
+        assert!(parse_program_name("06c").is_none());
+        assert!(parse_program_name("b").is_none());
+        assert!(parse_program_name("").is_none());
+    }
+
+    #[test]
+    fn test_program_selectors() {
+        let day01 = find_program(1).unwrap();
+        let top = Part::Variant(String::from("top"));
+        let params = Params::default().with("top", "5");
+        assert_eq!(
+            day01.selectors(None, Params::default()),
+            Ok(vec![Selector::from(Part::One), Selector::from(Part::Two)])
+        );
+        assert_eq!(
+            day01.selectors(None, params.clone()),
+            Ok(vec![Selector::new(top.clone(), params.clone())])
+        );
+        assert_eq!(
+            day01.selectors(Some(vec![Part::One, top.clone()]), params.clone()),
+            Ok(vec![
+                Selector::new(Part::One, params.clone()),
+                Selector::new(top, params)
+            ])
+        );
+        assert_eq!(
+            day01.selectors(None, Params::default().with("row", "10")),
+            Err(Error::Usage(String::from("Day 01 has no option --row")))
+        );
+        assert_eq!(
+            day01.selectors(Some(vec![Part::from_label("bottom")]), Params::default()),
+            Err(Error::Usage(String::from("Day 01 has no part bottom")))
+        );
+    }
+
+    #[test]
+    fn test_run_program_unknown() {
+        assert_eq!(
+            run_program("99a", ""),
+            Err(Error::UnknownProgram(String::from("99a")))
+        );
     }
 }
@@ -1,40 +1,161 @@
-use crate::parts::Parts;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parts::{OptionSpec, Params, Part, Selector};
+use crate::solver::Solver;
+use std::io::BufRead;
 
-pub fn run(part_number: Parts, input: &str) -> String {
-    let mut elf_calories = collect_elf_calories(input).to_owned();
-    let result = compute_result(part_number, &mut elf_calories);
-    format!("{}", result)
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+    const STREAMING: bool = true;
+    const VARIANTS: &'static [OptionSpec] = &[OptionSpec {
+        name: "top",
+        about: "Total calories carried by the <n> elves carrying the most",
+    }];
+
+    type Input<'a> = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_elf_calories(input)
+    }
+
+    fn part_one(elf_calories: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(max_elf_calories(elf_calories, 1)))
+    }
+
+    fn part_two(elf_calories: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(max_elf_calories(elf_calories, 3)))
+    }
+
+    fn variant(elf_calories: &Self::Input<'_>, name: &str, params: &Params) -> Result<Answer> {
+        let top_n = top_n_for(&Selector::new(Part::from_label(name), params.clone()))?;
+        Ok(Answer::from(max_elf_calories(elf_calories, top_n)))
+    }
+
+    fn stream(reader: &mut dyn BufRead, selectors: &[Selector]) -> Result<Vec<Answer>> {
+        let top_ns = selectors
+            .iter()
+            .map(top_n_for)
+            .collect::<Result<Vec<usize>>>()?;
+        let mut largest = LargestTotals::new(top_ns.iter().copied().max().unwrap_or_default());
+        let mut tally = ElfTally::default();
+        let mut line = String::new();
+        let mut line_number = 0;
+        loop {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .map_err(|error| Error::io("input", error))?;
+            if read == 0 {
+                break;
+            }
+            line_number += 1;
+            if let Some(total) = tally.add_line(line_number, &line)? {
+                largest.insert(total);
+            }
+        }
+        if let Some(total) = tally.finish() {
+            largest.insert(total);
+        }
+        Ok(top_ns
+            .iter()
+            .map(|&top_n| Answer::from(largest.sum(top_n)))
+            .collect())
+    }
 }
 
-fn compute_result(part_number: Parts, elf_calories: &mut Vec<i32>) -> i32 {
-    match part_number {
-        Parts::One => max_elf_calories(elf_calories, 1),
-        Parts::Two => max_elf_calories(elf_calories, 3),
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day01::run(selector, input)
+}
+
+fn top_n_for(selector: &Selector) -> Result<usize> {
+    match &selector.part {
+        Part::One => Ok(1),
+        Part::Two => Ok(3),
+        Part::Variant(name) if name == "top" => selector.params.require("top"),
+        Part::Variant(name) => Err(Error::Usage(format!("Day 01 has no variant {}", name))),
     }
 }
 
-fn max_elf_calories(elf_calories: &mut Vec<i32>, top_n: usize) -> i32 {
-    elf_calories.sort_by(|a, b| b.cmp(a));
-    elf_calories.truncate(top_n);
-    let result = elf_calories.iter().sum();
-    result
+// Each elf's total fits in an i32, but several of them added together may not.
+fn max_elf_calories(elf_calories: &[i32], top_n: usize) -> i64 {
+    let mut sorted_calories = elf_calories.to_vec();
+    sorted_calories.sort_by(|a, b| b.cmp(a));
+    sorted_calories.truncate(top_n);
+    sorted_calories.iter().map(|&total| i64::from(total)).sum()
 }
 
-fn collect_elf_calories(input: &str) -> Vec<i32> {
-    let mut elf_calories: Vec<i32> = vec![];
-    let mut running_total: i32 = 0;
-    for l in input.split('\n') {
+/// Sums calories line by line; a blank line finishes the current elf.
+#[derive(Default)]
+struct ElfTally {
+    running_total: i32,
+}
+
+impl ElfTally {
+    fn add_line(&mut self, line_number: usize, l: &str) -> Result<Option<i32>> {
+        let l = l.trim_end();
         if l.is_empty() {
-            elf_calories.push(running_total);
-            running_total = 0;
-        } else {
-            running_total += l.parse::<i32>().unwrap();
+            return Ok(Some(std::mem::take(&mut self.running_total)));
         }
+        let calories = l
+            .parse::<i32>()
+            .map_err(|_| Error::parse(line_number, 1, l, "expected a calorie count"))?;
+        self.running_total = self
+            .running_total
+            .checked_add(calories)
+            .ok_or_else(|| Error::parse(line_number, 1, l, "calorie total overflows"))?;
+        Ok(None)
     }
-    if running_total > 0 {
-        elf_calories.push(running_total);
+
+    fn finish(self) -> Option<i32> {
+        Some(self.running_total).filter(|&total| total > 0)
     }
-    elf_calories
+}
+
+/// Keeps only the `capacity` largest totals seen, largest first. `capacity` comes
+/// from `--top`, so the totals grow as elves are counted rather than up front.
+struct LargestTotals {
+    capacity: usize,
+    totals: Vec<i32>,
+}
+
+impl LargestTotals {
+    fn new(capacity: usize) -> Self {
+        LargestTotals {
+            capacity,
+            totals: vec![],
+        }
+    }
+
+    fn insert(&mut self, total: i32) {
+        let position = self.totals.partition_point(|&kept| kept >= total);
+        if position < self.capacity {
+            self.totals.insert(position, total);
+            self.totals.truncate(self.capacity);
+        }
+    }
+
+    fn sum(&self, top_n: usize) -> i64 {
+        self.totals
+            .iter()
+            .take(top_n)
+            .map(|&total| i64::from(total))
+            .sum()
+    }
+}
+
+fn collect_elf_calories(input: &str) -> Result<Vec<i32>> {
+    let mut elf_calories: Vec<i32> = vec![];
+    let mut tally = ElfTally::default();
+    for (index, l) in input.split('\n').enumerate() {
+        if let Some(total) = tally.add_line(index + 1, l)? {
+            elf_calories.push(total);
+        }
+    }
+    elf_calories.extend(tally.finish());
+    Ok(elf_calories)
 }
 
 #[cfg(test)]
@@ -49,19 +170,89 @@ mod tests {
     fn test_collect_elf_calories() {
         let fixture_file = "./data/day01/test.txt";
         let test_input = fs::read_to_string(fixture_file).expect("Failed to read input file");
-        let result = collect_elf_calories(&test_input);
+        let result = collect_elf_calories(&test_input).unwrap();
         let expected = vec![6000i32, 4000, 11000, 24000, 10000];
         assert!(vec_compare(&result, &expected));
     }
 
+    #[test]
+    fn test_collect_elf_calories_reports_bad_line() {
+        let result = collect_elf_calories("1000\n2000\n\n30x0\n");
+        assert_eq!(
+            result,
+            Err(Error::parse(4, 1, "30x0", "expected a calorie count"))
+        );
+    }
+
+    #[test]
+    fn test_stream_matches_parse() {
+        let fixture_file = "./data/day01/test.txt";
+        let test_input = fs::read_to_string(fixture_file).expect("Failed to read input file");
+        let selectors = [Selector::from(Part::One), Selector::from(Part::Two)];
+        let result = Day01::stream(&mut test_input.as_bytes(), &selectors);
+        assert_eq!(result, Ok(vec![Answer::from(24000), Answer::from(45000)]));
+    }
+
+    #[test]
+    fn test_top_variant() {
+        let fixture_file = "./data/day01/test.txt";
+        let test_input = fs::read_to_string(fixture_file).expect("Failed to read input file");
+        let top = |n: &str| {
+            Selector::new(
+                Part::Variant(String::from("top")),
+                Params::default().with("top", n),
+            )
+        };
+        assert_eq!(run(&top("2"), &test_input), Ok(Answer::from(35000)));
+        assert_eq!(run(&top("5"), &test_input), Ok(Answer::from(55000)));
+        assert_eq!(
+            Day01::stream(&mut test_input.as_bytes(), &[top("4")]),
+            Ok(vec![Answer::from(51000)])
+        );
+        let everyone = top("18446744073709551615");
+        assert_eq!(run(&everyone, &test_input), Ok(Answer::from(55000)));
+        assert_eq!(
+            Day01::stream(&mut test_input.as_bytes(), &[everyone]),
+            Ok(vec![Answer::from(55000)])
+        );
+        assert_eq!(
+            run(
+                &Selector::from(Part::Variant(String::from("top"))),
+                &test_input
+            ),
+            Err(Error::Usage(String::from("Missing value for --top")))
+        );
+    }
+
+    #[test]
+    fn test_largest_totals() {
+        let mut largest = LargestTotals::new(3);
+        [6000, 4000, 11000, 24000, 10000]
+            .iter()
+            .for_each(|&total| largest.insert(total));
+        assert_eq!(largest.totals, vec![24000, 11000, 10000]);
+        assert_eq!(largest.sum(1), 24000);
+    }
+
     #[test]
     fn test_max_elf_calories_single() {
-        let mut test_data = vec![6000i32, 4000, 11000, 24000, 10000];
-        assert_eq!(max_elf_calories(&mut test_data, 1), 24000);
+        let test_data = vec![6000i32, 4000, 11000, 24000, 10000];
+        assert_eq!(max_elf_calories(&test_data, 1), 24000);
     }
     #[test]
     fn test_max_elf_calories_top_n() {
-        let mut test_data = vec![6000i32, 4000, 11000, 24000, 10000];
-        assert_eq!(max_elf_calories(&mut test_data, 3), 45000);
+        let test_data = vec![6000i32, 4000, 11000, 24000, 10000];
+        assert_eq!(max_elf_calories(&test_data, 3), 45000);
+    }
+
+    #[test]
+    fn test_max_elf_calories_beyond_i32() {
+        let input = "2000000000\n\n2000000000\n";
+        let selector = Selector::from(Part::Two);
+        assert_eq!(run(&selector, input), Ok(Answer::from(4000000000i64)));
+        assert_eq!(
+            Day01::stream(&mut input.as_bytes(), &[selector]),
+            Ok(vec![Answer::from(4000000000i64)])
+        );
     }
 }
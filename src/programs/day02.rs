@@ -1,16 +1,38 @@
-use crate::parts::Parts;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
 
-pub fn run(part_number: Parts, input: &str) -> String {
-    let strategy_guide = collect_turns(input);
-    let result = compute_result(part_number, strategy_guide);
-    format!("{}", result)
-}
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Input<'a> = Vec<(&'a str, &'a str)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_turns(input)
+    }
 
-fn compute_result(part_number: Parts, strategy_guide: Vec<(&str, &str)>) -> i32 {
-    match part_number {
-        Parts::One => compute_all_turns_score(strategy_guide, RPSStrategy::Play),
-        Parts::Two => compute_all_turns_score(strategy_guide, RPSStrategy::Outcome),
+    fn part_one(strategy_guide: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(compute_all_turns_score(
+            strategy_guide,
+            RPSStrategy::Play,
+        )))
     }
+
+    fn part_two(strategy_guide: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(compute_all_turns_score(
+            strategy_guide,
+            RPSStrategy::Outcome,
+        )))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day02::run(selector, input)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -27,47 +49,47 @@ enum RPSStrategy {
 }
 
 impl RPSStrategy {
-    fn decode_turn(&self, opponent_code: &str, second_code: &str) -> RPSTurn {
+    fn decode_turn(&self, opponent_code: &str, second_code: &str) -> Option<RPSTurn> {
         match self {
             Self::Play => self.play_strategy(opponent_code, second_code),
             Self::Outcome => self.outcome_strategy(opponent_code, second_code),
         }
     }
 
-    fn opponent_play(&self, turn_code: &str) -> RPSPlay {
+    fn opponent_play(&self, turn_code: &str) -> Option<RPSPlay> {
         match turn_code {
-            "A" => RPSPlay::Rock,
-            "B" => RPSPlay::Paper,
-            "C" => RPSPlay::Scissors,
-            _ => panic!("Unknown turn code for opponent {}", turn_code),
+            "A" => Some(RPSPlay::Rock),
+            "B" => Some(RPSPlay::Paper),
+            "C" => Some(RPSPlay::Scissors),
+            _ => None,
         }
     }
 
-    fn play_strategy(&self, opponent_code: &str, player_code: &str) -> RPSTurn {
+    fn play_strategy(&self, opponent_code: &str, player_code: &str) -> Option<RPSTurn> {
         let player_play = match player_code {
             "X" => RPSPlay::Rock,
             "Y" => RPSPlay::Paper,
             "Z" => RPSPlay::Scissors,
-            _ => panic!("Unknown turn code for player {}", player_code),
+            _ => return None,
         };
-        RPSTurn {
-            opponent: self.opponent_play(opponent_code),
+        Some(RPSTurn {
+            opponent: self.opponent_play(opponent_code)?,
             player: player_play,
-        }
+        })
     }
 
-    fn outcome_strategy(&self, opponent_code: &str, outcome_code: &str) -> RPSTurn {
-        let opponent_play = self.opponent_play(opponent_code);
+    fn outcome_strategy(&self, opponent_code: &str, outcome_code: &str) -> Option<RPSTurn> {
+        let opponent_play = self.opponent_play(opponent_code)?;
         let outcome = match outcome_code {
             "X" => RPSTurnOutcome::Loss,
             "Y" => RPSTurnOutcome::Tie,
             "Z" => RPSTurnOutcome::Win,
-            _ => panic!("Unknown outcome code {}", outcome_code),
+            _ => return None,
         };
-        match outcome {
+        let turn = match outcome {
             RPSTurnOutcome::Tie => RPSTurn {
                 opponent: opponent_play,
-                player: opponent_play.clone(),
+                player: opponent_play,
             },
             RPSTurnOutcome::Win => RPSTurn {
                 opponent: opponent_play,
@@ -77,7 +99,8 @@ impl RPSStrategy {
                 opponent: opponent_play,
                 player: opponent_play.defeats(),
             },
-        }
+        };
+        Some(turn)
     }
 }
 
@@ -146,24 +169,51 @@ impl RPSTurn {
     }
 }
 
-fn compute_all_turns_score(strategy_guide: Vec<(&str, &str)>, strategy: RPSStrategy) -> i32 {
+// Codes are validated by `collect_turns`, so every turn decodes.
+fn compute_all_turns_score(strategy_guide: &[(&str, &str)], strategy: RPSStrategy) -> i32 {
     strategy_guide
         .iter()
-        .map(|(code1, code2)| strategy.decode_turn(code1, code2).score())
+        .filter_map(|(code1, code2)| strategy.decode_turn(code1, code2))
+        .map(|turn| turn.score())
         .sum()
 }
 
-fn collect_turns(input: &str) -> Vec<(&str, &str)> {
+fn collect_turns(input: &str) -> Result<Vec<(&str, &str)>> {
     let mut turns: Vec<(&str, &str)> = vec![];
-    for l in input.split('\n') {
-        if l.trim().is_empty() {
-            continue;
+    for (line_number, l) in numbered_lines(input) {
+        let code_pair = l.split_whitespace().collect::<Vec<_>>();
+        let (code1, code2) = match code_pair[..] {
+            [code1, code2] => (code1, code2),
+            _ => {
+                return Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected an opponent code and a response code",
+                ))
+            }
+        };
+        if RPSStrategy::Play.opponent_play(code1).is_none() {
+            let column = column_of(l, code1);
+            return Err(Error::parse(
+                line_number,
+                column,
+                code1,
+                "expected A, B or C",
+            ));
         }
-        let code_pair = l.trim().split(' ').collect::<Vec<_>>();
-        let turn = (code_pair[0], code_pair[1]);
-        turns.push(turn);
+        if !matches!(code2, "X" | "Y" | "Z") {
+            let column = column_of(l, code2);
+            return Err(Error::parse(
+                line_number,
+                column,
+                code2,
+                "expected X, Y or Z",
+            ));
+        }
+        turns.push((code1, code2));
     }
-    turns
+    Ok(turns)
 }
 
 #[cfg(test)]
@@ -178,22 +228,43 @@ mod tests {
     fn test_collect_turns() {
         let fixture_file = "./data/day02/test.txt";
         let test_input = fs::read_to_string(fixture_file).expect("Failed to read input file");
-        let result = collect_turns(&test_input);
+        let result = collect_turns(&test_input).unwrap();
         let expected = vec![("A", "Y"), ("B", "X"), ("C", "Z")];
         assert!(vec_compare(&result, &expected));
     }
 
+    #[test]
+    fn test_collect_turns_rejects_unknown_codes() {
+        assert_eq!(
+            collect_turns("A Y\nB W\n"),
+            Err(Error::parse(2, 3, "W", "expected X, Y or Z"))
+        );
+        assert_eq!(
+            collect_turns("D Y\n"),
+            Err(Error::parse(1, 1, "D", "expected A, B or C"))
+        );
+        assert_eq!(
+            collect_turns("A\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "A",
+                "expected an opponent code and a response code"
+            ))
+        );
+    }
+
     #[test]
     fn test_play_strategy() {
         let test_data = vec![("A", "Y"), ("B", "X"), ("C", "Z")];
-        let result = compute_all_turns_score(test_data, RPSStrategy::Play);
+        let result = compute_all_turns_score(&test_data, RPSStrategy::Play);
         assert_eq!(result, 15);
     }
 
     #[test]
     fn test_outcome_strategy() {
         let test_data = vec![("A", "Y"), ("B", "X"), ("C", "Z")];
-        let result = compute_all_turns_score(test_data, RPSStrategy::Outcome);
+        let result = compute_all_turns_score(&test_data, RPSStrategy::Outcome);
         assert_eq!(result, 12);
     }
 }
@@ -1,19 +1,78 @@
-use crate::parts::Parts;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
 
-pub fn run(part_number: Parts, input: &str) -> String {
-    let rucksacks = collect_rucksacks(input).to_owned();
-    let result = compute_result(part_number, rucksacks);
-    format!("{}", result)
+pub struct Day03;
+
+impl Solver for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Input<'a> = PackingList;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut line_numbers: Vec<usize> = vec![];
+        for (line_number, l) in numbered_lines(input) {
+            let rucksack = l.trim();
+            if let Some((offset, _)) = rucksack
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphabetic())
+            {
+                let column = column_of(l, &rucksack[offset..]);
+                return Err(Error::parse(
+                    line_number,
+                    column,
+                    l,
+                    "expected only item letters",
+                ));
+            }
+            if rucksack.len() % 2 != 0 {
+                return Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected an even number of items",
+                ));
+            }
+            line_numbers.push(line_number);
+        }
+        Ok(PackingList {
+            rucksacks: collect_rucksacks(input),
+            line_numbers,
+        })
+    }
+
+    fn part_one(packing_list: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        compute_misplaced_priority_sum(packing_list.rucksacks.clone())
+            .map(Answer::from)
+            .map_err(|index| packing_list.error_at(index, "no item appears in both compartments"))
+    }
+
+    fn part_two(packing_list: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        compute_badge_priority_sum(packing_list.rucksacks.clone())
+            .map(Answer::from)
+            .map_err(|index| packing_list.error_at(index, "no badge is shared by this team"))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day03::run(selector, input)
 }
 
 type Rucksack = String;
 type Compartments = (String, String);
 type Team = (Rucksack, Rucksack, Rucksack);
 
-fn compute_result(part_number: Parts, rucksacks: Vec<Rucksack>) -> i32 {
-    match part_number {
-        Parts::One => compute_misplaced_priority_sum(rucksacks),
-        Parts::Two => compute_badge_priority_sum(rucksacks),
+pub struct PackingList {
+    rucksacks: Vec<Rucksack>,
+    line_numbers: Vec<usize>,
+}
+
+impl PackingList {
+    fn error_at(&self, index: usize, message: &str) -> Error {
+        Error::parse(self.line_numbers[index], 1, &self.rucksacks[index], message)
     }
 }
 
@@ -28,46 +87,48 @@ fn priority_for_item(c: char) -> i32 {
     }
 }
 
-fn compute_misplaced_priority_sum(rucksacks: Vec<Rucksack>) -> i32 {
+// Errors carry the index of the first rucksack that could not be resolved.
+fn compute_misplaced_priority_sum(rucksacks: Vec<Rucksack>) -> std::result::Result<i32, usize> {
     let compartments = split_compartments(rucksacks);
-    let misplaced = find_misplaced(compartments);
-    misplaced.iter().map(|c| priority_for_item(*c)).sum()
+    let misplaced = find_misplaced(compartments)?;
+    Ok(misplaced.iter().map(|c| priority_for_item(*c)).sum())
 }
 
-fn compute_badge_priority_sum(rucksacks: Vec<String>) -> i32 {
-    let badges = find_badges(rucksacks);
-    badges.iter().map(|c| priority_for_item(*c)).sum()
+fn compute_badge_priority_sum(rucksacks: Vec<String>) -> std::result::Result<i32, usize> {
+    let badges = find_badges(rucksacks)?;
+    Ok(badges.iter().map(|c| priority_for_item(*c)).sum())
 }
 
-fn get_shared_among_team(rucksacks: Team) -> char {
-    let rucksack_one = rucksacks.0.chars();
-    let filtered = rucksack_one
-        .filter(|c| rucksacks.1.as_str().contains(*c))
-        .filter(|c| rucksacks.2.as_str().contains(*c))
-        .collect::<Vec<char>>();
-    filtered[0]
+fn get_shared_among_team(rucksacks: Team) -> Option<char> {
+    let mut rucksack_one = rucksacks.0.chars();
+    rucksack_one.find(|c| rucksacks.1.as_str().contains(*c) && rucksacks.2.as_str().contains(*c))
 }
 
-fn get_shared_in_rucksack(rucksack: &Compartments) -> char {
-    let compartment_one = rucksack.0.chars();
-    let filtered = compartment_one
-        .filter(|c| rucksack.1.as_str().contains(*c))
-        .collect::<Vec<char>>();
-    filtered[0]
+fn get_shared_in_rucksack(rucksack: &Compartments) -> Option<char> {
+    let mut compartment_one = rucksack.0.chars();
+    compartment_one.find(|c| rucksack.1.as_str().contains(*c))
 }
 
-fn find_misplaced(compartments: Vec<Compartments>) -> Vec<char> {
+fn find_misplaced(compartments: Vec<Compartments>) -> std::result::Result<Vec<char>, usize> {
     compartments
         .iter()
-        .map(get_shared_in_rucksack)
-        .collect::<Vec<char>>()
+        .enumerate()
+        .map(|(index, rucksack)| get_shared_in_rucksack(rucksack).ok_or(index))
+        .collect::<std::result::Result<Vec<char>, usize>>()
 }
 
-fn find_badges(rucksacks: Vec<Rucksack>) -> Vec<char> {
+fn find_badges(rucksacks: Vec<Rucksack>) -> std::result::Result<Vec<char>, usize> {
     rucksacks
         .chunks(3)
-        .map(|team| get_shared_among_team((team[0].clone(), team[1].clone(), team[2].clone())))
-        .collect::<Vec<char>>()
+        .enumerate()
+        .map(|(index, team)| match team {
+            [first, second, third] => {
+                get_shared_among_team((first.clone(), second.clone(), third.clone()))
+                    .ok_or(index * 3)
+            }
+            _ => Err(index * 3),
+        })
+        .collect::<std::result::Result<Vec<char>, usize>>()
 }
 
 fn split_compartments(rucksacks: Vec<Rucksack>) -> Vec<Compartments> {
@@ -174,7 +235,7 @@ mod tests {
         let expecteds = fixture_missing();
         TestCase::create_many(inputs, expecteds)
             .iter()
-            .for_each(|case| assert_eq!(get_shared_in_rucksack(&case.input), case.expected));
+            .for_each(|case| assert_eq!(get_shared_in_rucksack(&case.input), Some(case.expected)));
     }
 
     #[test]
@@ -185,7 +246,7 @@ mod tests {
             .iter()
             .for_each(|case| {
                 let input = case.input.clone();
-                assert_eq!(get_shared_among_team(input), case.expected);
+                assert_eq!(get_shared_among_team(input), Some(case.expected));
             });
     }
 
@@ -193,7 +254,7 @@ mod tests {
     fn test_find_misplaced() {
         let inputs = fixture_compartments();
         let expected = fixture_missing();
-        let results = find_misplaced(inputs);
+        let results = find_misplaced(inputs).unwrap();
         assert!(vec_compare(&results, &expected));
     }
 
@@ -201,13 +262,13 @@ mod tests {
     fn test_find_badges() {
         let inputs = fixture_rucksacks();
         let expected = fixture_badges();
-        let results = find_badges(inputs);
+        let results = find_badges(inputs).unwrap();
         assert!(vec_compare(&results, &expected));
     }
 
     #[test]
     fn test_priority_for_item() {
-        let cases = vec![
+        let cases = [
             TestCase {
                 input: 'a',
                 expected: 1i32,
@@ -234,13 +295,29 @@ mod tests {
     fn test_compute_misplaced_priority_sum() {
         let inputs = fixture_rucksacks();
         let result = compute_misplaced_priority_sum(inputs);
-        assert_eq!(result, 157);
+        assert_eq!(result, Ok(157));
     }
 
     #[test]
     fn test_compute_badge_priority_sum() {
         let inputs = fixture_rucksacks();
         let result = compute_badge_priority_sum(inputs);
-        assert_eq!(result, 70);
+        assert_eq!(result, Ok(70));
+    }
+
+    #[test]
+    fn test_find_badges_reports_incomplete_team() {
+        let mut inputs = fixture_rucksacks();
+        inputs.push(String::from("vJrwpWtwJgWrhcsFMMfFFhFp"));
+        assert_eq!(find_badges(inputs), Err(6));
+    }
+
+    #[test]
+    fn test_parse_reports_bad_item() {
+        let result = Day03::parse("vJrwpWtwJgWrhcsFMMfFFhFp\nabc1\n");
+        assert_eq!(
+            result.err(),
+            Some(Error::parse(2, 4, "abc1", "expected only item letters"))
+        );
     }
 }
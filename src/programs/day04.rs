@@ -1,30 +1,44 @@
-use crate::parts::Parts;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::parse_field;
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
 
-pub fn run(part_number: Parts, input: &str) -> String {
-    let strategy_guide = collect_assignments(input);
-    let result = compute_result(part_number, strategy_guide);
-    format!("{}", result)
-}
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Input<'a> = Vec<PairAssignment>;
 
-fn compute_result(part_number: Parts, assignments: Vec<String>) -> i32 {
-    match part_number {
-        Parts::One => compute_fully_overlapping_pairs(assignments),
-        Parts::Two => compute_overlapping_pairs(assignments),
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_assignment_pairs(collect_assignments(input))
     }
+
+    fn part_one(pairs: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(compute_fully_overlapping_pairs(pairs)))
+    }
+
+    fn part_two(pairs: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(compute_overlapping_pairs(pairs)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day04::run(selector, input)
 }
 
-fn compute_fully_overlapping_pairs(assignments: Vec<String>) -> i32 {
-    let pairs = collect_assignment_pairs(assignments);
+fn compute_fully_overlapping_pairs(pairs: &[PairAssignment]) -> i32 {
     pairs.iter().filter(|pair| pair.full_overlap()).count() as i32
 }
 
-fn compute_overlapping_pairs(assignments: Vec<String>) -> i32 {
-    let pairs = collect_assignment_pairs(assignments);
+fn compute_overlapping_pairs(pairs: &[PairAssignment]) -> i32 {
     pairs.iter().filter(|pair| pair.overlap()).count() as i32
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-struct Assignment {
+pub struct Assignment {
     start_section: i32,
     end_section: i32,
 }
@@ -35,7 +49,7 @@ impl Assignment {
     }
 }
 
-type PairAssignment = Vec<Assignment>;
+pub type PairAssignment = Vec<Assignment>;
 
 trait Overlap {
     fn full_overlap(&self) -> bool;
@@ -81,39 +95,46 @@ impl Overlap for PairAssignment {
     }
 }
 
-fn collect_assignment_pairs(assignments: Vec<String>) -> Vec<PairAssignment> {
+fn parse_assignment(line_number: usize, line: &str, elf: &str) -> Result<Assignment> {
+    let (start, end) = elf
+        .split_once('-')
+        .ok_or_else(|| Error::parse(line_number, 1, elf, "expected a section range like 2-4"))?;
+    Ok(Assignment {
+        start_section: parse_field(line_number, line, start, "expected a section number")?,
+        end_section: parse_field(line_number, line, end, "expected a section number")?,
+    })
+}
+
+fn parse_assignment_pair(line_number: usize, line: &str) -> Result<PairAssignment> {
+    let (elf1, elf2) = line.split_once(',').ok_or_else(|| {
+        Error::parse(
+            line_number,
+            1,
+            line,
+            "expected two comma-separated assignments",
+        )
+    })?;
+    Ok(vec![
+        parse_assignment(line_number, line, elf1)?,
+        parse_assignment(line_number, line, elf2)?,
+    ])
+}
+
+// Assignments are one per input line, so blank lines are kept to preserve line numbers.
+fn collect_assignment_pairs(assignments: Vec<String>) -> Result<Vec<PairAssignment>> {
     assignments
         .iter()
-        .map(|assignment| {
-            let elves = assignment.split(',').collect::<Vec<&str>>();
-            let elf1 = elves[0]
-                .split('-')
-                .map(|val| val.parse::<i32>().unwrap().clone())
-                .collect::<Vec<i32>>();
-            let elf2 = elves[1]
-                .split('-')
-                .map(|val| val.parse::<i32>().unwrap().clone())
-                .collect::<Vec<i32>>();
-            vec![
-                Assignment {
-                    start_section: elf1[0],
-                    end_section: elf1[1],
-                },
-                Assignment {
-                    start_section: elf2[0],
-                    end_section: elf2[1],
-                },
-            ]
-        })
-        .collect::<Vec<PairAssignment>>()
+        .enumerate()
+        .filter(|(_, assignment)| !assignment.is_empty())
+        .map(|(index, assignment)| parse_assignment_pair(index + 1, assignment))
+        .collect::<Result<Vec<PairAssignment>>>()
 }
 
 fn collect_assignments(input: &str) -> Vec<String> {
     input
+        .trim_end()
         .lines()
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| String::from(l))
+        .map(|l| String::from(l.trim()))
         .collect::<Vec<String>>()
 }
 
@@ -267,7 +288,26 @@ mod tests {
     fn test_collect_assignment_pairs() {
         let inputs = fixture_assignments();
         let expecteds = fixture_assignment_pairs();
-        assert_eq!(collect_assignment_pairs(inputs), expecteds);
+        assert_eq!(collect_assignment_pairs(inputs), Ok(expecteds));
+    }
+
+    #[test]
+    fn test_collect_assignment_pairs_reports_malformed_pairs() {
+        let inputs = vec![String::from("2-4,6-8"), String::from("2-3")];
+        assert_eq!(
+            collect_assignment_pairs(inputs),
+            Err(Error::parse(
+                2,
+                1,
+                "2-3",
+                "expected two comma-separated assignments"
+            ))
+        );
+        let inputs = vec![String::from("2-4,6-x")];
+        assert_eq!(
+            collect_assignment_pairs(inputs),
+            Err(Error::parse(1, 7, "x", "expected a section number"))
+        );
     }
 
     #[test]
@@ -305,15 +345,15 @@ mod tests {
 
     #[test]
     fn test_compute_fully_overlapping_pairs() {
-        let test_data = fixture_assignments();
-        let result = compute_fully_overlapping_pairs(test_data);
+        let test_data = fixture_assignment_pairs();
+        let result = compute_fully_overlapping_pairs(&test_data);
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_compute_overlapping_pairs() {
-        let test_data = fixture_assignments();
-        let result = compute_overlapping_pairs(test_data);
+        let test_data = fixture_assignment_pairs();
+        let result = compute_overlapping_pairs(&test_data);
         assert_eq!(result, 4);
     }
 }
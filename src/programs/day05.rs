@@ -0,0 +1,281 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::fmt;
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Input<'a> = Procedure;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_procedure(input)
+    }
+
+    fn part_one(procedure: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        procedure
+            .rearrange(CrateMover::Model9000)
+            .map(|stacks| Answer::from(top_crates(&stacks)))
+    }
+
+    fn part_two(procedure: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        procedure
+            .rearrange(CrateMover::Model9001)
+            .map(|stacks| Answer::from(top_crates(&stacks)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day05::run(selector, input)
+}
+
+/// Bottom-to-top crates on each stack, in stack order.
+type Stacks = Vec<Vec<char>>;
+
+#[derive(Clone, Copy, Debug)]
+enum CrateMover {
+    /// Moves one crate at a time, so a moved group ends up reversed.
+    Model9000,
+    /// Moves a whole group at once, keeping its order.
+    Model9001,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    line_number: usize,
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Procedure {
+    stacks: Stacks,
+    moves: Vec<Move>,
+}
+
+impl Procedure {
+    fn rearrange(&self, crane: CrateMover) -> Result<Stacks> {
+        let mut stacks = self.stacks.clone();
+        for step in &self.moves {
+            let source = &mut stacks[step.from - 1];
+            if source.len() < step.count {
+                let message = format!(
+                    "expected at most {} crates on stack {}",
+                    source.len(),
+                    step.from
+                );
+                let text = step.to_string();
+                return Err(Error::parse(step.line_number, 1, &text, &message));
+            }
+            let mut lifted = source.split_off(source.len() - step.count);
+            if let CrateMover::Model9000 = crane {
+                lifted.reverse();
+            }
+            stacks[step.to - 1].extend(lifted);
+        }
+        Ok(stacks)
+    }
+}
+
+fn top_crates(stacks: &Stacks) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+// The drawing's crates sit in every fourth column, e.g. `[Z] [M] [P]`, above a
+// line of stack numbers; trailing spaces may have been trimmed from any row.
+fn parse_drawing(rows: &[(usize, &str)]) -> Result<Stacks> {
+    let (&(numbers_line, numbers), crate_rows) = rows
+        .split_last()
+        .ok_or_else(|| Error::parse(1, 1, "", "expected a drawing of crate stacks"))?;
+    let labels = numbers.split_whitespace().collect::<Vec<&str>>();
+    for (index, label) in labels.iter().enumerate() {
+        if label.parse::<usize>() != Ok(index + 1) {
+            let message = format!("expected stack number {}", index + 1);
+            return Err(Error::parse(
+                numbers_line,
+                column_of(numbers, label),
+                label,
+                &message,
+            ));
+        }
+    }
+
+    let mut stacks: Stacks = vec![vec![]; labels.len()];
+    for &(line_number, row) in crate_rows.iter().rev() {
+        let cells = row.chars().collect::<Vec<char>>();
+        for (start, cell) in cells.chunks(4).enumerate() {
+            match cell {
+                [' ', ' ', ' ', ..] | [' ', ' '] | [' '] => {}
+                ['[', label, ']', ..] if label.is_ascii_uppercase() && start < stacks.len() => {
+                    stacks[start].push(*label)
+                }
+                _ => {
+                    let text = cell.iter().collect::<String>();
+                    let (column, message) = (start * 4 + 1, "expected a crate like [A]");
+                    return Err(Error::parse(line_number, column, text.trim_end(), message));
+                }
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+fn parse_move(line_number: usize, line: &str, stack_count: usize) -> Result<Move> {
+    let malformed = || Error::parse(line_number, 1, line, "expected `move N from A to B`");
+    let fields = line.split_whitespace().collect::<Vec<&str>>();
+    let (count, from, to) = match fields[..] {
+        ["move", count, "from", from, "to", to] => (count, from, to),
+        _ => return Err(malformed()),
+    };
+    let stack = |field: &str| -> Result<usize> {
+        let number = parse_field::<usize>(line_number, line, field, "expected a stack number")?;
+        match (1..=stack_count).contains(&number) {
+            true => Ok(number),
+            false => {
+                let message = format!("expected a stack number from 1 to {}", stack_count);
+                Err(Error::parse(
+                    line_number,
+                    column_of(line, field),
+                    field,
+                    &message,
+                ))
+            }
+        }
+    };
+    Ok(Move {
+        line_number,
+        count: parse_field(line_number, line, count, "expected a crate count")?,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
+}
+
+fn collect_procedure(input: &str) -> Result<Procedure> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, l)| (index + 1, l.trim_end()))
+        .collect::<Vec<(usize, &str)>>();
+    let split = lines
+        .iter()
+        .position(|(_, l)| l.is_empty())
+        .unwrap_or(lines.len());
+    let stacks = parse_drawing(&lines[..split])?;
+    let moves = lines[split..]
+        .iter()
+        .filter(|(_, l)| !l.is_empty())
+        .map(|&(line_number, l)| parse_move(line_number, l.trim_start(), stacks.len()))
+        .collect::<Result<Vec<Move>>>()?;
+    Ok(Procedure { stacks, moves })
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::TestCase;
+
+    use std::fs;
+
+    fn fixture_procedure() -> Procedure {
+        let step = |line_number, count, from, to| Move {
+            line_number,
+            count,
+            from,
+            to,
+        };
+        Procedure {
+            stacks: vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']],
+            moves: vec![
+                step(6, 1, 2, 1),
+                step(7, 3, 1, 3),
+                step(8, 2, 2, 1),
+                step(9, 1, 1, 2),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_collect_procedure() {
+        let fixture_file = "./data/day05/test.txt";
+        let test_input = fs::read_to_string(fixture_file).expect("Failed to read input file");
+        assert_eq!(collect_procedure(&test_input), Ok(fixture_procedure()));
+    }
+
+    #[test]
+    fn test_collect_procedure_reports_bad_lines() {
+        let cases = [
+            (
+                "[A] [B]\n 1  2\n\nmove 1 from 1 to 3\n",
+                Error::parse(4, 18, "3", "expected a stack number from 1 to 2"),
+            ),
+            (
+                "[A] [B]\n 1  2\n\nmove one from 1 to 2\n",
+                Error::parse(4, 6, "one", "expected a crate count"),
+            ),
+            (
+                "[A] [B]\n 1  2\n\nshift 1 from 1 to 2\n",
+                Error::parse(4, 1, "shift 1 from 1 to 2", "expected `move N from A to B`"),
+            ),
+            (
+                "[A] (B)\n 1  2\n",
+                Error::parse(1, 5, "(B)", "expected a crate like [A]"),
+            ),
+            (
+                "[A] [B]\n 1  3\n",
+                Error::parse(2, 5, "3", "expected stack number 2"),
+            ),
+        ];
+        cases.into_iter().for_each(|(input, error)| {
+            assert_eq!(collect_procedure(input), Err(error));
+        });
+    }
+
+    #[test]
+    fn test_rearrange() {
+        let procedure = fixture_procedure();
+        let cases = TestCase::create_many(
+            vec![0, 1],
+            vec![
+                vec![vec!['C'], vec!['M'], vec!['P', 'D', 'N', 'Z']],
+                vec![vec!['M'], vec!['C'], vec!['P', 'Z', 'N', 'D']],
+            ],
+        );
+        cases.iter().for_each(|case| {
+            let crane = [CrateMover::Model9000, CrateMover::Model9001][case.input];
+            assert_eq!(procedure.rearrange(crane), Ok(case.expected.clone()));
+        });
+    }
+
+    #[test]
+    fn test_rearrange_reports_missing_crates() {
+        let procedure = collect_procedure("[A]\n 1  2\n\nmove 2 from 1 to 2\n").unwrap();
+        assert_eq!(
+            procedure.rearrange(CrateMover::Model9001),
+            Err(Error::parse(
+                4,
+                1,
+                "move 2 from 1 to 2",
+                "expected at most 1 crates on stack 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_top_crates() {
+        let stacks = vec![vec!['C'], vec![], vec!['P', 'D', 'Z']];
+        assert_eq!(top_crates(&stacks), "CZ");
+    }
+}
@@ -1,17 +1,183 @@
-use crate::parts::Parts;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parts::{OptionSpec, Params, Part, Selector};
+use crate::solver::Solver;
+use std::collections::VecDeque;
+use std::io::BufRead;
 
 pub const PACKET_MARKER_LENGTH: usize = 4;
 pub const MESSAGE_MARKER_LENGTH: usize = 14;
 
-pub fn run(part_number: Parts, input: &str) -> String {
-    let result = compute_result(part_number, &input);
-    format!("{}", result)
+/// No marker can be longer than the alphabet, since its letters are all different.
+const LETTERS: usize = 26;
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+    const STREAMING: bool = true;
+    const VARIANTS: &'static [OptionSpec] = &[OptionSpec {
+        name: "marker-length",
+        about: "Position after the first <n> distinct characters in a row",
+    }];
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let datastream = input.trim_end();
+        match datastream
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_lowercase())
+        {
+            Some((offset, c)) => {
+                let line = datastream[..offset].matches('\n').count() + 1;
+                let line_start = datastream[..offset].rfind('\n').map_or(0, |i| i + 1);
+                let column = datastream[line_start..offset].chars().count() + 1;
+                Err(Error::parse(
+                    line,
+                    column,
+                    &c.to_string(),
+                    "expected a lowercase letter",
+                ))
+            }
+            None => Ok(datastream),
+        }
+    }
+
+    fn part_one(datastream: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(find_marker(datastream, PACKET_MARKER_LENGTH)))
+    }
+
+    fn part_two(datastream: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(find_marker(datastream, MESSAGE_MARKER_LENGTH)))
+    }
+
+    fn variant(datastream: &Self::Input<'_>, name: &str, params: &Params) -> Result<Answer> {
+        let selector = Selector::new(Part::from_label(name), params.clone());
+        Ok(Answer::from(find_marker(
+            datastream,
+            marker_length_for(&selector)?,
+        )))
+    }
+
+    // Reading stops as soon as every requested marker has been found.
+    fn stream(reader: &mut dyn BufRead, selectors: &[Selector]) -> Result<Vec<Answer>> {
+        let mut detectors = selectors
+            .iter()
+            .map(|selector| Ok(MarkerDetector::new(marker_length_for(selector)?)))
+            .collect::<Result<Vec<MarkerDetector>>>()?;
+        let mut position = 0;
+        let (mut line, mut column) = (1, 0);
+        let mut trailing_whitespace = false;
+        loop {
+            let buffer = reader
+                .fill_buf()
+                .map_err(|error| Error::io("input", error))?;
+            if buffer.is_empty() || detectors.iter().all(MarkerDetector::is_done) {
+                break;
+            }
+            for &byte in buffer {
+                column += 1;
+                if byte.is_ascii_whitespace() {
+                    if byte == b'\n' {
+                        (line, column) = (line + 1, 0);
+                    }
+                    trailing_whitespace = true;
+                    continue;
+                }
+                position += 1;
+                if trailing_whitespace || !byte.is_ascii_lowercase() {
+                    let text = String::from_utf8_lossy(&[byte]).into_owned();
+                    return Err(Error::parse(
+                        line,
+                        column,
+                        &text,
+                        "expected a lowercase letter",
+                    ));
+                }
+                detectors
+                    .iter_mut()
+                    .for_each(|detector| detector.push(byte, position));
+            }
+            let consumed = buffer.len();
+            reader.consume(consumed);
+        }
+        Ok(detectors
+            .iter()
+            .map(|detector| Answer::from(detector.found))
+            .collect())
+    }
 }
 
-fn compute_result(part_number: Parts, input: &str) -> i32 {
-    match part_number {
-        Parts::One => find_marker(input, PACKET_MARKER_LENGTH),
-        Parts::Two => find_marker(input, MESSAGE_MARKER_LENGTH),
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day06::run(selector, input)
+}
+
+fn marker_length_for(selector: &Selector) -> Result<usize> {
+    match &selector.part {
+        Part::One => Ok(PACKET_MARKER_LENGTH),
+        Part::Two => Ok(MESSAGE_MARKER_LENGTH),
+        Part::Variant(name) if name == "marker-length" => {
+            match selector.params.require("marker-length")? {
+                0 => Err(Error::Usage(String::from(
+                    "Invalid value 0 for --marker-length",
+                ))),
+                marker_length => Ok(marker_length),
+            }
+        }
+        Part::Variant(name) => Err(Error::Usage(format!("Day 06 has no variant {}", name))),
+    }
+}
+
+/// Tracks the last `marker_length` letters of a stream and counts how many of
+/// them are repeated, so each new letter is checked in constant time.
+struct MarkerDetector {
+    marker_length: usize,
+    window: VecDeque<u8>,
+    counts: [usize; LETTERS],
+    repeated: usize,
+    found: Option<usize>,
+}
+
+impl MarkerDetector {
+    fn new(marker_length: usize) -> Self {
+        MarkerDetector {
+            marker_length,
+            window: VecDeque::new(),
+            counts: [0; LETTERS],
+            repeated: 0,
+            found: None,
+        }
+    }
+
+    /// Whether the marker has been found or is too long ever to be.
+    fn is_done(&self) -> bool {
+        self.found.is_some() || self.marker_length > LETTERS
+    }
+
+    fn push(&mut self, letter: u8, position: usize) {
+        if self.is_done() {
+            return;
+        }
+        let index = (letter - b'a') as usize;
+        self.counts[index] += 1;
+        if self.counts[index] == 2 {
+            self.repeated += 1;
+        }
+        self.window.push_back(letter);
+        if self.window.len() > self.marker_length {
+            if let Some(oldest) = self.window.pop_front() {
+                let index = (oldest - b'a') as usize;
+                self.counts[index] -= 1;
+                if self.counts[index] == 1 {
+                    self.repeated -= 1;
+                }
+            }
+        }
+        if self.window.len() == self.marker_length && self.repeated == 0 {
+            self.found = Some(position);
+        }
     }
 }
 
@@ -22,22 +188,15 @@ fn has_duplicated_chars(data: &str) -> bool {
     data_chars.len() < data.len()
 }
 
-fn find_marker(data: &str, marker_length: usize) -> i32 {
-    for n in marker_length..data.len() {
-        if let Some(candidate_marker) = data.get(n - marker_length..n) {
-            if candidate_marker.len() < marker_length {
-                panic!("n = {}, candidate_marker = '{}', marker_length = {}, data = '{}', error: candidate_marker.len() < 4", n, candidate_marker, marker_length, data);
-            }
-            // process the chunk
-            if !has_duplicated_chars(candidate_marker) {
-                return (n).try_into().unwrap();
-            }
-        } else {
-            // end of string
-            panic!("Did not find a start of packet marker.");
-        }
+fn find_marker(data: &str, marker_length: usize) -> Option<usize> {
+    if marker_length > LETTERS {
+        return None;
     }
-    -1
+    // windows that split a multi-byte character cannot be a marker
+    (marker_length..=data.len()).find(|&n| {
+        data.get(n - marker_length..n)
+            .is_some_and(|candidate_marker| !has_duplicated_chars(candidate_marker))
+    })
 }
 
 #[cfg(test)]
@@ -111,8 +270,8 @@ mod tests {
     fn test_find_marker_from_fixture_data() {
         let fixture_file = "./data/day06/test.txt";
         let test_input = fs::read_to_string(fixture_file).expect("Failed to read input file");
-        let packet_expected = 7;
-        let message_expected = 19;
+        let packet_expected = Some(7);
+        let message_expected = Some(19);
         assert_eq!(
             find_marker(&test_input, PACKET_MARKER_LENGTH),
             packet_expected
@@ -123,9 +282,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_marker_not_found() {
+        assert_eq!(find_marker("abcabc", PACKET_MARKER_LENGTH), None);
+        assert_eq!(find_marker("abcd", PACKET_MARKER_LENGTH), Some(4));
+        assert_eq!(find_marker("ab", PACKET_MARKER_LENGTH), None);
+        assert_eq!(
+            Day06::run(&Selector::from(Part::One), "abcabc"),
+            Ok(Answer::None)
+        );
+    }
+
+    #[test]
+    fn test_stream_matches_find_marker() {
+        let cases = [
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            "nppdvjthqldpwncqszvftbrmjlhg",
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+            "abcabc",
+        ];
+        cases.iter().for_each(|case| {
+            let input = format!("{}\n", case);
+            let reader = &mut std::io::BufReader::with_capacity(3, input.as_bytes());
+            let expected = vec![
+                Answer::from(find_marker(case, PACKET_MARKER_LENGTH)),
+                Answer::from(find_marker(case, MESSAGE_MARKER_LENGTH)),
+            ];
+            let selectors = [Selector::from(Part::One), Selector::from(Part::Two)];
+            assert_eq!(Day06::stream(reader, &selectors), Ok(expected));
+        });
+        assert_eq!(
+            Day06::stream(&mut "abc\ndef".as_bytes(), &[Selector::from(Part::One)]),
+            Err(Error::parse(2, 1, "d", "expected a lowercase letter"))
+        );
+    }
+
+    #[test]
+    fn test_marker_length_variant() {
+        let marker_length = |n: &str| {
+            Selector::new(
+                Part::Variant(String::from("marker-length")),
+                Params::default().with("marker-length", n),
+            )
+        };
+        let datastream = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(run(&marker_length("4"), datastream), Ok(Answer::from(7)));
+        assert_eq!(run(&marker_length("20"), datastream), Ok(Answer::None));
+        for too_long in ["27", "100000000000000", "18446744073709551615"] {
+            assert_eq!(run(&marker_length(too_long), datastream), Ok(Answer::None));
+            assert_eq!(
+                Day06::stream(&mut datastream.as_bytes(), &[marker_length(too_long)]),
+                Ok(vec![Answer::None])
+            );
+        }
+        assert_eq!(
+            Day06::stream(&mut datastream.as_bytes(), &[marker_length("14")]),
+            Ok(vec![Answer::from(19)])
+        );
+        assert_eq!(
+            run(&marker_length("0"), datastream),
+            Err(Error::Usage(String::from(
+                "Invalid value 0 for --marker-length"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unexpected_characters() {
+        assert_eq!(
+            Day06::parse("abcdEfg\n"),
+            Err(Error::parse(1, 5, "E", "expected a lowercase letter"))
+        );
+    }
+
     #[test]
     fn test_find_marker() {
-        let packet_test_data = vec![
+        let packet_test_data = [
             TestCase {
                 input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
                 expected: 7,
@@ -147,7 +381,7 @@ mod tests {
                 expected: 11,
             },
         ];
-        let message_test_data = vec![
+        let message_test_data = [
             TestCase {
                 input: "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
                 expected: 19,
@@ -171,12 +405,15 @@ mod tests {
         ];
 
         packet_test_data.iter().for_each(|case| {
-            assert_eq!(find_marker(case.input, PACKET_MARKER_LENGTH), case.expected);
+            assert_eq!(
+                find_marker(case.input, PACKET_MARKER_LENGTH),
+                Some(case.expected)
+            );
         });
         message_test_data.iter().for_each(|case| {
             assert_eq!(
                 find_marker(case.input, MESSAGE_MARKER_LENGTH),
-                case.expected
+                Some(case.expected)
             );
         });
     }
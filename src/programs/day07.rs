@@ -0,0 +1,249 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+
+pub const SMALL_DIRECTORY_LIMIT: u64 = 100_000;
+pub const TOTAL_DISK_SPACE: u64 = 70_000_000;
+pub const REQUIRED_UNUSED_SPACE: u64 = 30_000_000;
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    type Input<'a> = FileSystem;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        replay_transcript(input)
+    }
+
+    fn part_one(file_system: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(sum_small_directories(file_system)))
+    }
+
+    fn part_two(file_system: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(smallest_directory_to_delete(file_system)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day07::run(selector, input)
+}
+
+const ROOT: usize = 0;
+
+#[derive(Debug, PartialEq)]
+struct Directory {
+    name: String,
+    parent: Option<usize>,
+    subdirectories: Vec<usize>,
+    file_sizes: u64,
+    listed: bool,
+}
+
+/// Directories kept in one list and linked by index; the root is always first.
+#[derive(Debug, PartialEq)]
+pub struct FileSystem {
+    directories: Vec<Directory>,
+}
+
+impl FileSystem {
+    fn new() -> Self {
+        FileSystem {
+            directories: vec![Directory {
+                name: String::from("/"),
+                parent: None,
+                subdirectories: vec![],
+                file_sizes: 0,
+                listed: false,
+            }],
+        }
+    }
+
+    fn subdirectory(&self, directory: usize, name: &str) -> Option<usize> {
+        self.directories[directory]
+            .subdirectories
+            .iter()
+            .copied()
+            .find(|&child| self.directories[child].name == name)
+    }
+
+    fn add_subdirectory(&mut self, directory: usize, name: &str) {
+        self.directories.push(Directory {
+            name: String::from(name),
+            parent: Some(directory),
+            subdirectories: vec![],
+            file_sizes: 0,
+            listed: false,
+        });
+        let child = self.directories.len() - 1;
+        self.directories[directory].subdirectories.push(child);
+    }
+
+    /// Total size of every directory, including everything below it, by index.
+    fn directory_sizes(&self) -> Vec<u64> {
+        let mut sizes = self
+            .directories
+            .iter()
+            .map(|directory| directory.file_sizes)
+            .collect::<Vec<u64>>();
+        // Children are always added after their parents, so walking backwards
+        // finishes each directory before it is added to its parent.
+        for (index, directory) in self.directories.iter().enumerate().rev() {
+            if let Some(parent) = directory.parent {
+                sizes[parent] += sizes[index];
+            }
+        }
+        sizes
+    }
+}
+
+fn sum_small_directories(file_system: &FileSystem) -> u64 {
+    file_system
+        .directory_sizes()
+        .into_iter()
+        .filter(|&size| size <= SMALL_DIRECTORY_LIMIT)
+        .sum()
+}
+
+fn smallest_directory_to_delete(file_system: &FileSystem) -> Option<u64> {
+    let sizes = file_system.directory_sizes();
+    let unused = TOTAL_DISK_SPACE.saturating_sub(sizes[ROOT]);
+    let needed = REQUIRED_UNUSED_SPACE.saturating_sub(unused);
+    sizes.into_iter().filter(|&size| size >= needed).min()
+}
+
+/// What the transcript line being read is expected to be.
+#[derive(Clone, Copy, PartialEq)]
+enum Reading {
+    Command,
+    Listing,
+    /// Entries of a directory that was already listed, which must not be counted twice.
+    Relisting,
+}
+
+fn replay_transcript(input: &str) -> Result<FileSystem> {
+    let mut file_system = FileSystem::new();
+    let mut current = ROOT;
+    let mut reading = Reading::Command;
+    for (line_number, l) in numbered_lines(input) {
+        let words = l.split_whitespace().collect::<Vec<&str>>();
+        match words[..] {
+            ["$", "cd", target] => {
+                reading = Reading::Command;
+                current = match target {
+                    "/" => ROOT,
+                    ".." => file_system.directories[current].parent.unwrap_or(ROOT),
+                    name => file_system.subdirectory(current, name).ok_or_else(|| {
+                        Error::parse(
+                            line_number,
+                            1,
+                            l,
+                            "expected a directory that has been listed",
+                        )
+                    })?,
+                };
+            }
+            ["$", "ls"] => {
+                let directory = &mut file_system.directories[current];
+                reading = match directory.listed {
+                    true => Reading::Relisting,
+                    false => Reading::Listing,
+                };
+                directory.listed = true;
+            }
+            ["$", ..] => return Err(Error::parse(line_number, 1, l, "expected cd or ls")),
+            _ if reading == Reading::Command => {
+                return Err(Error::parse(line_number, 1, l, "expected a command"));
+            }
+            ["dir", name] => {
+                if reading == Reading::Listing {
+                    file_system.add_subdirectory(current, name);
+                }
+            }
+            [size, _] => {
+                let size: u64 = parse_field(line_number, l, size, "expected a file size")?;
+                if reading == Reading::Listing {
+                    file_system.directories[current].file_sizes += size;
+                }
+            }
+            _ => {
+                let message = "expected `dir <name>` or `<size> <name>`";
+                return Err(Error::parse(line_number, 1, l, message));
+            }
+        }
+    }
+    Ok(file_system)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, vec_compare};
+
+    fn fixture_file_system() -> FileSystem {
+        replay_transcript(&read_fixture(7, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_replay_transcript() {
+        let file_system = fixture_file_system();
+        let names = file_system
+            .directories
+            .iter()
+            .map(|directory| directory.name.as_str())
+            .collect::<Vec<&str>>();
+        assert!(vec_compare(&names, &["/", "a", "d", "e"]));
+        assert_eq!(file_system.directories[3].parent, Some(1));
+        assert_eq!(file_system.directories[3].file_sizes, 584);
+    }
+
+    #[test]
+    fn test_replay_transcript_reports_bad_lines() {
+        let cases = [
+            (
+                "$ cd /\n$ cd a\n",
+                Error::parse(2, 1, "$ cd a", "expected a directory that has been listed"),
+            ),
+            ("$ pwd\n", Error::parse(1, 1, "$ pwd", "expected cd or ls")),
+            ("dir a\n", Error::parse(1, 1, "dir a", "expected a command")),
+            (
+                "$ ls\n12k b.txt\n",
+                Error::parse(2, 1, "12k", "expected a file size"),
+            ),
+        ];
+        cases.into_iter().for_each(|(input, error)| {
+            assert_eq!(replay_transcript(input), Err(error));
+        });
+    }
+
+    #[test]
+    fn test_listing_twice_does_not_double_count() {
+        let transcript = "$ ls\ndir a\n5 g\n$ cd a\n$ ls\n10 f\n$ cd ..\n$ ls\ndir a\n5 g\n";
+        let file_system = replay_transcript(transcript).unwrap();
+        assert_eq!(file_system.directory_sizes(), vec![15, 10]);
+    }
+
+    #[test]
+    fn test_directory_sizes() {
+        let sizes = fixture_file_system().directory_sizes();
+        assert!(vec_compare(&sizes, &[48381165, 94853, 24933642, 584]));
+    }
+
+    #[test]
+    fn test_sum_small_directories() {
+        assert_eq!(sum_small_directories(&fixture_file_system()), 95437);
+    }
+
+    #[test]
+    fn test_smallest_directory_to_delete() {
+        assert_eq!(
+            smallest_directory_to_delete(&fixture_file_system()),
+            Some(24933642)
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Input<'a> = TreeGrid;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_tree_grid(input)
+    }
+
+    fn part_one(trees: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(count_visible_trees(trees)))
+    }
+
+    fn part_two(trees: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(max_scenic_score(trees)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day08::run(selector, input)
+}
+
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+#[derive(Debug, PartialEq)]
+pub struct TreeGrid {
+    heights: Vec<Vec<u8>>,
+}
+
+impl TreeGrid {
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.heights.len())
+            .flat_map(move |row| (0..self.heights[row].len()).map(move |column| (row, column)))
+    }
+
+    /// Heights of the trees between `(row, column)` and the edge in one direction,
+    /// nearest first.
+    fn line_of_sight(
+        &self,
+        (row, column): (usize, usize),
+        (row_step, column_step): (isize, isize),
+    ) -> impl Iterator<Item = u8> + '_ {
+        (1..).map_while(move |distance: isize| {
+            let row = row.checked_add_signed(row_step * distance)?;
+            let column = column.checked_add_signed(column_step * distance)?;
+            self.heights.get(row)?.get(column).copied()
+        })
+    }
+
+    fn is_visible(&self, position: (usize, usize)) -> bool {
+        let height = self.heights[position.0][position.1];
+        DIRECTIONS.iter().any(|&direction| {
+            self.line_of_sight(position, direction)
+                .all(|other| other < height)
+        })
+    }
+
+    fn scenic_score(&self, position: (usize, usize)) -> usize {
+        let height = self.heights[position.0][position.1];
+        DIRECTIONS
+            .iter()
+            .map(|&direction| {
+                let mut viewing_distance = 0;
+                for other in self.line_of_sight(position, direction) {
+                    viewing_distance += 1;
+                    if other >= height {
+                        break;
+                    }
+                }
+                viewing_distance
+            })
+            .product()
+    }
+}
+
+fn count_visible_trees(trees: &TreeGrid) -> usize {
+    trees
+        .positions()
+        .filter(|&position| trees.is_visible(position))
+        .count()
+}
+
+fn max_scenic_score(trees: &TreeGrid) -> usize {
+    trees
+        .positions()
+        .map(|position| trees.scenic_score(position))
+        .max()
+        .unwrap_or_default()
+}
+
+fn collect_tree_grid(input: &str) -> Result<TreeGrid> {
+    let mut heights: Vec<Vec<u8>> = vec![];
+    for (line_number, l) in numbered_lines(input) {
+        let row = l.trim();
+        if let Some((offset, _)) = row.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            let column = column_of(l, &row[offset..]);
+            return Err(Error::parse(
+                line_number,
+                column,
+                l,
+                "expected only tree heights 0-9",
+            ));
+        }
+        if heights
+            .first()
+            .is_some_and(|first| first.len() != row.len())
+        {
+            return Err(Error::parse(
+                line_number,
+                1,
+                l,
+                "expected rows of equal length",
+            ));
+        }
+        heights.push(row.bytes().map(|b| b - b'0').collect());
+    }
+    Ok(TreeGrid { heights })
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_tree_grid() -> TreeGrid {
+        TreeGrid {
+            heights: vec![
+                vec![3, 0, 3, 7, 3],
+                vec![2, 5, 5, 1, 2],
+                vec![6, 5, 3, 3, 2],
+                vec![3, 3, 5, 4, 9],
+                vec![3, 5, 3, 9, 0],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_collect_tree_grid() {
+        let test_input = read_fixture(8, "test.txt");
+        assert_eq!(collect_tree_grid(&test_input), Ok(fixture_tree_grid()));
+    }
+
+    #[test]
+    fn test_collect_tree_grid_reports_bad_lines() {
+        assert_eq!(
+            collect_tree_grid("303\n2x5\n"),
+            Err(Error::parse(2, 2, "2x5", "expected only tree heights 0-9"))
+        );
+        assert_eq!(
+            collect_tree_grid("303\n25\n"),
+            Err(Error::parse(2, 1, "25", "expected rows of equal length"))
+        );
+    }
+
+    #[test]
+    fn test_is_visible() {
+        let trees = fixture_tree_grid();
+        let positions = vec![
+            (0, 0),
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (2, 1),
+            (2, 2),
+            (2, 3),
+            (3, 2),
+        ];
+        let expecteds = vec![true, true, true, false, true, false, true, true];
+        TestCase::create_many(positions, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(trees.is_visible(case.input), case.expected);
+            });
+    }
+
+    #[test]
+    fn test_scenic_score() {
+        let trees = fixture_tree_grid();
+        let positions = vec![(1, 2), (3, 2), (0, 0), (2, 2)];
+        let expecteds = vec![4, 8, 0, 1];
+        TestCase::create_many(positions, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(trees.scenic_score(case.input), case.expected);
+            });
+    }
+
+    #[test]
+    fn test_count_visible_trees() {
+        assert_eq!(count_visible_trees(&fixture_tree_grid()), 21);
+    }
+
+    #[test]
+    fn test_max_scenic_score() {
+        assert_eq!(max_scenic_score(&fixture_tree_grid()), 8);
+    }
+}
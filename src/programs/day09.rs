@@ -0,0 +1,218 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines, parse_field};
+use crate::parts::{OptionSpec, Params, Part, Selector};
+use crate::solver::Solver;
+use std::collections::HashSet;
+
+/// The most knots `--knots` accepts; every knot is simulated on every step.
+pub const MAX_KNOTS: usize = 10_000;
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+    const VARIANTS: &'static [OptionSpec] = &[OptionSpec {
+        name: "knots",
+        about: "Positions visited by the tail of a rope with <n> knots (1 to 10000)",
+    }];
+
+    type Input<'a> = Vec<Motion>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_motions(input)
+    }
+
+    fn part_one(motions: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(count_tail_positions(motions, 2)))
+    }
+
+    fn part_two(motions: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(count_tail_positions(motions, 10)))
+    }
+
+    fn variant(motions: &Self::Input<'_>, name: &str, params: &Params) -> Result<Answer> {
+        let selector = Selector::new(Part::from_label(name), params.clone());
+        Ok(Answer::from(count_tail_positions(
+            motions,
+            knots_for(&selector)?,
+        )))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day09::run(selector, input)
+}
+
+fn knots_for(selector: &Selector) -> Result<usize> {
+    match &selector.part {
+        Part::One => Ok(2),
+        Part::Two => Ok(10),
+        Part::Variant(name) if name == "knots" => match selector.params.require("knots")? {
+            knots if (1..=MAX_KNOTS).contains(&knots) => Ok(knots),
+            knots => Err(Error::Usage(format!("Invalid value {} for --knots", knots))),
+        },
+        Part::Variant(name) => Err(Error::Usage(format!("Day 09 has no variant {}", name))),
+    }
+}
+
+type Position = (i32, i32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Motion {
+    step: Position,
+    count: usize,
+}
+
+/// Moves a knot one step towards the knot ahead of it, if they no longer touch.
+fn follow(knot: Position, ahead: Position) -> Position {
+    let (dx, dy) = (ahead.0 - knot.0, ahead.1 - knot.1);
+    if dx.abs() <= 1 && dy.abs() <= 1 {
+        knot
+    } else {
+        (knot.0 + dx.signum(), knot.1 + dy.signum())
+    }
+}
+
+fn count_tail_positions(motions: &[Motion], knots: usize) -> usize {
+    let mut rope: Vec<Position> = vec![(0, 0); knots];
+    let mut visited: HashSet<Position> = HashSet::from([(0, 0)]);
+    for motion in motions {
+        for _ in 0..motion.count {
+            rope[0] = (rope[0].0 + motion.step.0, rope[0].1 + motion.step.1);
+            for index in 1..rope.len() {
+                rope[index] = follow(rope[index], rope[index - 1]);
+            }
+            visited.extend(rope.last());
+        }
+    }
+    visited.len()
+}
+
+fn collect_motions(input: &str) -> Result<Vec<Motion>> {
+    numbered_lines(input)
+        .map(|(line_number, l)| {
+            let (direction, count) = l.trim().split_once(' ').ok_or_else(|| {
+                Error::parse(line_number, 1, l, "expected a direction and a step count")
+            })?;
+            let step = match direction {
+                "U" => (0, 1),
+                "D" => (0, -1),
+                "L" => (-1, 0),
+                "R" => (1, 0),
+                _ => {
+                    let (column, message) = (column_of(l, direction), "expected U, D, L or R");
+                    return Err(Error::parse(line_number, column, direction, message));
+                }
+            };
+            Ok(Motion {
+                step,
+                count: parse_field(line_number, l, count, "expected a step count")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_motions(name: &str) -> Vec<Motion> {
+        collect_motions(&read_fixture(9, name)).unwrap()
+    }
+
+    #[test]
+    fn test_collect_motions() {
+        let motions = fixture_motions("test.txt");
+        assert_eq!(motions.len(), 8);
+        assert_eq!(
+            motions[0],
+            Motion {
+                step: (1, 0),
+                count: 4
+            }
+        );
+        assert_eq!(
+            motions[1],
+            Motion {
+                step: (0, 1),
+                count: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_collect_motions_reports_bad_lines() {
+        assert_eq!(
+            collect_motions("R 4\nX 2\n"),
+            Err(Error::parse(2, 1, "X", "expected U, D, L or R"))
+        );
+        assert_eq!(
+            collect_motions("R four\n"),
+            Err(Error::parse(1, 3, "four", "expected a step count"))
+        );
+        assert_eq!(
+            collect_motions("R\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "R",
+                "expected a direction and a step count"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_follow() {
+        let inputs = vec![
+            ((0, 0), (1, 1)),
+            ((0, 0), (2, 0)),
+            ((0, 0), (2, 1)),
+            ((0, 0), (-1, -2)),
+            ((0, 0), (2, 2)),
+        ];
+        let expecteds = vec![(0, 0), (1, 0), (1, 1), (-1, -1), (1, 1)];
+        TestCase::create_many(inputs, expecteds)
+            .iter()
+            .for_each(|case| {
+                let (knot, ahead) = case.input;
+                assert_eq!(follow(knot, ahead), case.expected);
+            });
+    }
+
+    #[test]
+    fn test_count_tail_positions() {
+        let small = fixture_motions("test.txt");
+        let large = fixture_motions("test-large.txt");
+        assert_eq!(count_tail_positions(&small, 2), 13);
+        assert_eq!(count_tail_positions(&small, 10), 1);
+        assert_eq!(count_tail_positions(&large, 10), 36);
+        assert_eq!(count_tail_positions(&small, 1), 21);
+    }
+
+    #[test]
+    fn test_knots_variant() {
+        let knots = |n: &str| {
+            Selector::new(
+                Part::Variant(String::from("knots")),
+                Params::default().with("knots", n),
+            )
+        };
+        let input = read_fixture(9, "test-large.txt");
+        assert_eq!(run(&knots("10"), &input), Ok(Answer::from(36)));
+        assert_eq!(run(&knots("2"), &input), Ok(Answer::from(88)));
+        assert_eq!(
+            run(&knots("0"), &input),
+            Err(Error::Usage(String::from("Invalid value 0 for --knots")))
+        );
+        assert_eq!(
+            run(&knots("10000000000000"), &input),
+            Err(Error::Usage(String::from(
+                "Invalid value 10000000000000 for --knots"
+            )))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::iter;
+
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Input<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_instructions(input)
+    }
+
+    fn part_one(program: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(sum_signal_strengths(program)))
+    }
+
+    fn part_two(program: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::Grid(render_crt(program)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day10::run(selector, input)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+}
+
+/// The value of the X register during each cycle, starting with cycle 1.
+fn register_values(program: &[Instruction]) -> Vec<i32> {
+    let mut x = 1;
+    let mut values: Vec<i32> = vec![];
+    for instruction in program {
+        values.extend(iter::repeat_n(x, instruction.cycles()));
+        if let Instruction::Addx(value) = instruction {
+            x += value;
+        }
+    }
+    values
+}
+
+fn sum_signal_strengths(program: &[Instruction]) -> i32 {
+    register_values(program)
+        .iter()
+        .enumerate()
+        .map(|(index, &x)| (index as i32 + 1, x))
+        .filter(|(cycle, _)| cycle % 40 == 20 && *cycle <= 220)
+        .map(|(cycle, x)| cycle * x)
+        .sum()
+}
+
+// The sprite is three pixels wide and centred on X; the CRT draws one pixel per
+// cycle, left to right and top to bottom.
+fn render_crt(program: &[Instruction]) -> Vec<String> {
+    let values = register_values(program);
+    (0..CRT_HEIGHT)
+        .map(|row| {
+            (0..CRT_WIDTH)
+                .map(|column| {
+                    let sprite = values.get(row * CRT_WIDTH + column);
+                    match sprite {
+                        Some(x) if (x - column as i32).abs() <= 1 => '#',
+                        _ => '.',
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn collect_instructions(input: &str) -> Result<Vec<Instruction>> {
+    numbered_lines(input)
+        .map(
+            |(line_number, l)| match l.split_whitespace().collect::<Vec<&str>>()[..] {
+                ["noop"] => Ok(Instruction::Noop),
+                ["addx", value] => Ok(Instruction::Addx(parse_field(
+                    line_number,
+                    l,
+                    value,
+                    "expected a number to add",
+                )?)),
+                _ => Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected noop or addx <number>",
+                )),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, vec_compare, TestCase};
+
+    fn fixture_program() -> Vec<Instruction> {
+        collect_instructions(&read_fixture(10, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_instructions() {
+        let result = collect_instructions("noop\naddx 3\naddx -5\n").unwrap();
+        let expected = vec![
+            Instruction::Noop,
+            Instruction::Addx(3),
+            Instruction::Addx(-5),
+        ];
+        assert!(vec_compare(&result, &expected));
+        assert_eq!(
+            collect_instructions("noop\naddx x\n"),
+            Err(Error::parse(2, 6, "x", "expected a number to add"))
+        );
+        assert_eq!(
+            collect_instructions("jmp 4\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "jmp 4",
+                "expected noop or addx <number>"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_register_values() {
+        let program = collect_instructions("noop\naddx 3\naddx -5\n").unwrap();
+        assert_eq!(register_values(&program), vec![1, 1, 1, 4, 4]);
+    }
+
+    #[test]
+    fn test_register_values_at_signal_cycles() {
+        let values = register_values(&fixture_program());
+        let cycles = vec![20, 60, 100, 140, 180, 220];
+        let expecteds = vec![21, 19, 18, 21, 16, 18];
+        TestCase::create_many(cycles, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(values[case.input - 1], case.expected);
+            });
+    }
+
+    #[test]
+    fn test_sum_signal_strengths() {
+        assert_eq!(sum_signal_strengths(&fixture_program()), 13140);
+    }
+
+    #[test]
+    fn test_render_crt() {
+        let expected = vec![
+            "##..##..##..##..##..##..##..##..##..##..",
+            "###...###...###...###...###...###...###.",
+            "####....####....####....####....####....",
+            "#####.....#####.....#####.....#####.....",
+            "######......######......######......####",
+            "#######.......#######.......#######.....",
+        ];
+        assert_eq!(render_crt(&fixture_program()), expected);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    type Input<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_monkeys(input)
+    }
+
+    fn part_one(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(monkey_business(
+            monkeys,
+            20,
+            Relief::DivideByThree,
+        )?))
+    }
+
+    fn part_two(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(monkey_business(
+            monkeys,
+            10_000,
+            Relief::None,
+        )?))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day11::run(selector, input)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Relief {
+    DivideByThree,
+    None,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operand {
+    Old,
+    Value(u64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operation {
+    Add(Operand),
+    Multiply(Operand),
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it does not fit in a u64.
+    fn apply(&self, old: u64) -> Option<u64> {
+        let value = |operand: &Operand| match operand {
+            Operand::Old => old,
+            Operand::Value(value) => *value,
+        };
+        match self {
+            Operation::Add(operand) => old.checked_add(value(operand)),
+            Operation::Multiply(operand) => old.checked_mul(value(operand)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+impl Monkey {
+    fn target(&self, worry: u64) -> usize {
+        match worry % self.divisor {
+            0 => self.if_true,
+            _ => self.if_false,
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+fn least_common_multiple(monkeys: &[Monkey]) -> Option<u64> {
+    monkeys.iter().try_fold(1u64, |lcm, monkey| {
+        (lcm / gcd(lcm, monkey.divisor)).checked_mul(monkey.divisor)
+    })
+}
+
+// Every test only asks whether a worry level is divisible by some monkey's
+// divisor, so without relief worry levels can be kept modulo the divisors' least
+// common multiple. (Relief divides by three, which does not commute with that.)
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: Relief) -> Result<u64> {
+    let modulus = match relief {
+        Relief::DivideByThree => None,
+        Relief::None => Some(least_common_multiple(monkeys).ok_or_else(|| {
+            Error::Failed(String::from(
+                "the divisors' least common multiple does not fit in a u64",
+            ))
+        })?),
+    };
+    let mut monkeys = monkeys.to_vec();
+    if let Some(modulus) = modulus {
+        for item in monkeys
+            .iter_mut()
+            .flat_map(|monkey| monkey.items.iter_mut())
+        {
+            *item %= modulus;
+        }
+    }
+    let mut inspections: Vec<u64> = vec![0; monkeys.len()];
+    for _ in 0..rounds {
+        for index in 0..monkeys.len() {
+            let items = std::mem::take(&mut monkeys[index].items);
+            inspections[index] += items.len() as u64;
+            for item in items {
+                let worry = monkeys[index].operation.apply(item).ok_or_else(|| {
+                    Error::Failed(format!(
+                        "monkey {} raises a worry level of {} beyond a u64",
+                        index, item
+                    ))
+                })?;
+                let worry = match modulus {
+                    Some(modulus) => worry % modulus,
+                    None => worry / 3,
+                };
+                let target = monkeys[index].target(worry);
+                monkeys[target].items.push(worry);
+            }
+        }
+    }
+    inspections.sort_by(|a, b| b.cmp(a));
+    inspections[..inspections.len().min(2)]
+        .iter()
+        .try_fold(1u64, |product, &count| product.checked_mul(count))
+        .ok_or_else(|| Error::Failed(String::from("monkey business does not fit in a u64")))
+}
+
+/// The rest of `l` after `prefix`, ignoring indentation.
+fn after<'a>(line_number: usize, l: &'a str, prefix: &str) -> Result<&'a str> {
+    l.trim_start().strip_prefix(prefix).ok_or_else(|| {
+        let message = format!("expected `{}`", prefix.trim_end());
+        Error::parse(line_number, 1, l, &message)
+    })
+}
+
+fn parse_operation(line_number: usize, l: &str) -> Result<Operation> {
+    let expression = after(line_number, l, "Operation: new = old ")?;
+    let malformed = || Error::parse(line_number, 1, l, "expected `new = old <+|*> <operand>`");
+    let (operator, operand) = expression.split_once(' ').ok_or_else(malformed)?;
+    let operand = match operand {
+        "old" => Operand::Old,
+        value => Operand::Value(parse_field(
+            line_number,
+            l,
+            value,
+            "expected a number or old",
+        )?),
+    };
+    match operator {
+        "+" => Ok(Operation::Add(operand)),
+        "*" => Ok(Operation::Multiply(operand)),
+        _ => {
+            let column = column_of(l, operator);
+            Err(Error::parse(
+                line_number,
+                column,
+                operator,
+                "expected + or *",
+            ))
+        }
+    }
+}
+
+fn parse_monkey(lines: &[(usize, &str)], id: usize, monkey_count: usize) -> Result<Monkey> {
+    let line = |index: usize| lines[index];
+    let (line_number, l) = line(0);
+    let label = after(line_number, l, "Monkey ")?.trim_end_matches(':');
+    if label.parse::<usize>() != Ok(id) {
+        let message = format!("expected monkey {}", id);
+        return Err(Error::parse(
+            line_number,
+            column_of(l, label),
+            label,
+            &message,
+        ));
+    }
+
+    let (line_number, l) = line(1);
+    let items = after(line_number, l, "Starting items:")?
+        .split(',')
+        .filter(|item| !item.trim().is_empty())
+        .map(|item| parse_field(line_number, l, item, "expected a worry level"))
+        .collect::<Result<Vec<u64>>>()?;
+
+    let (line_number, l) = line(2);
+    let operation = parse_operation(line_number, l)?;
+
+    let (line_number, l) = line(3);
+    let divisor = after(line_number, l, "Test: divisible by ")?;
+    let divisor = match parse_field(line_number, l, divisor, "expected a divisor")? {
+        0 => {
+            let column = column_of(l, divisor);
+            return Err(Error::parse(
+                line_number,
+                column,
+                divisor,
+                "expected a non-zero divisor",
+            ));
+        }
+        divisor => divisor,
+    };
+
+    let target = |index: usize, prefix: &str| -> Result<usize> {
+        let (line_number, l) = line(index);
+        let field = after(line_number, l, prefix)?;
+        match parse_field(line_number, l, field, "expected a monkey number")? {
+            target if target < monkey_count && target != id => Ok(target),
+            _ => {
+                let column = column_of(l, field);
+                Err(Error::parse(
+                    line_number,
+                    column,
+                    field,
+                    "expected another monkey",
+                ))
+            }
+        }
+    };
+    Ok(Monkey {
+        items,
+        operation,
+        divisor,
+        if_true: target(4, "If true: throw to monkey ")?,
+        if_false: target(5, "If false: throw to monkey ")?,
+    })
+}
+
+fn collect_monkeys(input: &str) -> Result<Vec<Monkey>> {
+    let lines = numbered_lines(input).collect::<Vec<(usize, &str)>>();
+    let monkey_count = lines.len() / 6;
+    lines
+        .chunks(6)
+        .enumerate()
+        .map(|(id, block)| match block.len() {
+            6 => parse_monkey(block, id, monkey_count),
+            _ => {
+                let (line_number, l) = block[block.len() - 1];
+                Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected six lines per monkey",
+                ))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_monkeys() -> Vec<Monkey> {
+        collect_monkeys(&read_fixture(11, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_monkeys() {
+        let monkeys = fixture_monkeys();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(
+            monkeys[0],
+            Monkey {
+                items: vec![79, 98],
+                operation: Operation::Multiply(Operand::Value(19)),
+                divisor: 23,
+                if_true: 2,
+                if_false: 3,
+            }
+        );
+        assert_eq!(monkeys[2].operation, Operation::Multiply(Operand::Old));
+        assert_eq!(monkeys[3].operation, Operation::Add(Operand::Value(3)));
+    }
+
+    #[test]
+    fn test_collect_monkeys_reports_bad_lines() {
+        let monkey = |operation: &str, target: &str| {
+            format!(
+                "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = old {}\n  \
+                 Test: divisible by 23\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey 1\n\nMonkey 1:\n  Starting items:\n  \
+                 Operation: new = old + 1\n  Test: divisible by 2\n    \
+                 If true: throw to monkey 0\n    If false: throw to monkey 0\n",
+                operation, target
+            )
+        };
+        assert!(collect_monkeys(&monkey("* 19", "1")).is_ok());
+        assert_eq!(
+            collect_monkeys(&monkey("- 19", "1")),
+            Err(Error::parse(3, 24, "-", "expected + or *"))
+        );
+        assert_eq!(
+            collect_monkeys(&monkey("* x", "1")),
+            Err(Error::parse(3, 26, "x", "expected a number or old"))
+        );
+        assert_eq!(
+            collect_monkeys(&monkey("* 19", "7")),
+            Err(Error::parse(5, 30, "7", "expected another monkey"))
+        );
+        assert_eq!(
+            collect_monkeys("Monkey 1:\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "Monkey 1:",
+                "expected six lines per monkey"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_operation_apply() {
+        let operations = vec![
+            Operation::Multiply(Operand::Value(19)),
+            Operation::Add(Operand::Value(6)),
+            Operation::Multiply(Operand::Old),
+        ];
+        let expecteds = vec![1501, 85, 6241];
+        TestCase::create_many(operations, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(case.input.apply(79), Some(case.expected));
+            });
+        assert_eq!(Operation::Multiply(Operand::Old).apply(9_700_000_000), None);
+    }
+
+    #[test]
+    fn test_monkey_business() {
+        let monkeys = fixture_monkeys();
+        assert_eq!(
+            monkey_business(&monkeys, 20, Relief::DivideByThree),
+            Ok(10605)
+        );
+        assert_eq!(
+            monkey_business(&monkeys, 10_000, Relief::None),
+            Ok(2713310158)
+        );
+    }
+
+    #[test]
+    fn test_monkey_business_with_large_worry_levels() {
+        let mut monkeys = fixture_monkeys();
+        monkeys[2].items = vec![79, 60, 9_700_000_000];
+        assert_eq!(
+            monkey_business(&monkeys, 20, Relief::DivideByThree),
+            Err(Error::Failed(String::from(
+                "monkey 2 raises a worry level of 9700000000 beyond a u64"
+            )))
+        );
+        assert!(monkey_business(&monkeys, 10_000, Relief::None).is_ok());
+        for (monkey, divisor) in monkeys.iter_mut().zip([4294967291, 4294967279, 3, 5]) {
+            monkey.divisor = divisor;
+        }
+        assert_eq!(
+            monkey_business(&monkeys, 10_000, Relief::None),
+            Err(Error::Failed(String::from(
+                "the divisors' least common multiple does not fit in a u64"
+            )))
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::collections::VecDeque;
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+
+    type Input<'a> = Heightmap;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_heightmap(input)
+    }
+
+    fn part_one(heightmap: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let steps = heightmap.steps_to_best_signal();
+        Ok(Answer::from(steps[heightmap.start.0][heightmap.start.1]))
+    }
+
+    fn part_two(heightmap: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let steps = heightmap.steps_to_best_signal();
+        Ok(Answer::from(heightmap.fewest_steps_from(&steps, 0)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day12::run(selector, input)
+}
+
+type Position = (usize, usize);
+
+#[derive(Debug, PartialEq)]
+pub struct Heightmap {
+    /// Elevations from 0 (`a`) to 25 (`z`).
+    elevations: Vec<Vec<u8>>,
+    start: Position,
+    end: Position,
+}
+
+impl Heightmap {
+    fn neighbours(&self, (row, column): Position) -> impl Iterator<Item = Position> + '_ {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(move |(row_step, column_step)| {
+                let row = row.checked_add_signed(row_step)?;
+                let column = column.checked_add_signed(column_step)?;
+                self.elevations.get(row)?.get(column)?;
+                Some((row, column))
+            })
+    }
+
+    /// Fewest steps from every square to the end, found by searching backwards from
+    /// the end: a step down may be at most one lower, since climbing may be at most
+    /// one higher.
+    fn steps_to_best_signal(&self) -> Vec<Vec<Option<usize>>> {
+        let mut steps = self
+            .elevations
+            .iter()
+            .map(|row| vec![None; row.len()])
+            .collect::<Vec<Vec<Option<usize>>>>();
+        steps[self.end.0][self.end.1] = Some(0);
+        let mut queue = VecDeque::from([(self.end, 0)]);
+        while let Some((position, distance)) = queue.pop_front() {
+            let elevation = self.elevations[position.0][position.1];
+            for next in self.neighbours(position) {
+                let reachable = self.elevations[next.0][next.1] + 1 >= elevation;
+                if reachable && steps[next.0][next.1].is_none() {
+                    steps[next.0][next.1] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        steps
+    }
+
+    fn fewest_steps_from(&self, steps: &[Vec<Option<usize>>], elevation: u8) -> Option<usize> {
+        self.elevations
+            .iter()
+            .zip(steps)
+            .flat_map(|(elevations, steps)| elevations.iter().zip(steps))
+            .filter(|(&square, _)| square == elevation)
+            .filter_map(|(_, &steps)| steps)
+            .min()
+    }
+}
+
+fn collect_heightmap(input: &str) -> Result<Heightmap> {
+    let mut elevations: Vec<Vec<u8>> = vec![];
+    let (mut start, mut end) = (None, None);
+    let mut last_line = (0, "");
+    for (line_number, l) in numbered_lines(input) {
+        let row = l.trim();
+        let mut elevation_row: Vec<u8> = vec![];
+        for (offset, c) in row.char_indices() {
+            let position = (elevations.len(), elevation_row.len());
+            let elevation = match c {
+                'S' if start.is_none() => {
+                    start = Some(position);
+                    'a'
+                }
+                'E' if end.is_none() => {
+                    end = Some(position);
+                    'z'
+                }
+                'a'..='z' => c,
+                _ => {
+                    let text = &row[offset..offset + c.len_utf8()];
+                    let (column, message) = (column_of(l, text), "expected a-z or one S and E");
+                    return Err(Error::parse(line_number, column, text, message));
+                }
+            };
+            elevation_row.push(elevation as u8 - b'a');
+        }
+        elevations.push(elevation_row);
+        last_line = (line_number, l);
+    }
+    match (start, end) {
+        (Some(start), Some(end)) => Ok(Heightmap {
+            elevations,
+            start,
+            end,
+        }),
+        _ => Err(Error::parse(
+            last_line.0,
+            1,
+            last_line.1,
+            "expected a start S and an end E",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_heightmap() -> Heightmap {
+        collect_heightmap(&read_fixture(12, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_heightmap() {
+        let heightmap = fixture_heightmap();
+        assert_eq!(heightmap.start, (0, 0));
+        assert_eq!(heightmap.end, (2, 5));
+        assert_eq!(heightmap.elevations[0], vec![0, 0, 1, 16, 15, 14, 13, 12]);
+        assert_eq!(heightmap.elevations[2][5], 25);
+    }
+
+    #[test]
+    fn test_collect_heightmap_reports_bad_lines() {
+        assert_eq!(
+            collect_heightmap("Sab\naE1\n"),
+            Err(Error::parse(2, 3, "1", "expected a-z or one S and E"))
+        );
+        assert_eq!(
+            collect_heightmap("SaS\naEb\n"),
+            Err(Error::parse(1, 3, "S", "expected a-z or one S and E"))
+        );
+        assert_eq!(
+            collect_heightmap("Sab\nabc\n"),
+            Err(Error::parse(2, 1, "abc", "expected a start S and an end E"))
+        );
+    }
+
+    #[test]
+    fn test_steps_to_best_signal() {
+        let heightmap = fixture_heightmap();
+        let steps = heightmap.steps_to_best_signal();
+        let positions = vec![(0, 0), (2, 5), (2, 4), (4, 0), (0, 3)];
+        let expecteds = vec![Some(31), Some(0), Some(1), Some(29), Some(12)];
+        TestCase::create_many(positions, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(steps[case.input.0][case.input.1], case.expected);
+            });
+    }
+
+    #[test]
+    fn test_fewest_steps_from() {
+        let heightmap = fixture_heightmap();
+        let steps = heightmap.steps_to_best_signal();
+        assert_eq!(heightmap.fewest_steps_from(&steps, 0), Some(29));
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        let heightmap = collect_heightmap("SaE\n").unwrap();
+        assert_eq!(
+            Day12::part_one(&heightmap, &Params::default()),
+            Ok(Answer::None)
+        );
+    }
+}
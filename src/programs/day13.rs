@@ -0,0 +1,337 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The deepest nesting of lists accepted, which bounds the recursion in parsing,
+/// comparing and printing packets.
+pub const MAX_DEPTH: usize = 100;
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    type Input<'a> = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_pairs(input)
+    }
+
+    fn part_one(pairs: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(sum_ordered_pair_indices(pairs)))
+    }
+
+    fn part_two(pairs: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(decoder_key(pairs)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day13::run(selector, input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    // Integers compare by value and lists element by element, with the shorter list
+    // first on a tie; an integer compared with a list is treated as a one-item list.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(left), Packet::Integer(right)) => left.cmp(right),
+            (Packet::List(left), Packet::List(right)) => left.cmp(right),
+            (Packet::Integer(_), Packet::List(right)) => std::slice::from_ref(self).cmp(right),
+            (Packet::List(left), Packet::Integer(_)) => left[..].cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Integer(value) => write!(f, "{}", value),
+            Packet::List(items) => {
+                let items = items.iter().map(Packet::to_string).collect::<Vec<String>>();
+                write!(f, "[{}]", items.join(","))
+            }
+        }
+    }
+}
+
+/// The left and right packets of one pair.
+pub type Pair = (Packet, Packet);
+
+fn sum_ordered_pair_indices(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (left, right))| left < right)
+        .map(|(index, _)| index + 1)
+        .sum()
+}
+
+fn divider(value: u32) -> Packet {
+    Packet::List(vec![Packet::List(vec![Packet::Integer(value)])])
+}
+
+// Sorting is not needed: a divider's position is one more than the number of
+// packets (including the other divider) that sort before it.
+fn decoder_key(pairs: &[Pair]) -> usize {
+    let packets = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .collect::<Vec<&Packet>>();
+    let (first, second) = (divider(2), divider(6));
+    let first_position = packets.iter().filter(|&&packet| *packet < first).count() + 1;
+    let second_position = packets.iter().filter(|&&packet| *packet < second).count() + 2;
+    first_position * second_position
+}
+
+/// Reads one packet from a line, keeping the byte offset for error columns and how
+/// many lists deep it is.
+struct PacketParser<'a> {
+    line_number: usize,
+    line: &'a str,
+    offset: usize,
+    depth: usize,
+}
+
+impl PacketParser<'_> {
+    fn error(&self, message: &str) -> Error {
+        let text = self.line[self.offset..]
+            .chars()
+            .next()
+            .map_or("", |c| &self.line[self.offset..self.offset + c.len_utf8()]);
+        Error::parse(self.line_number, self.offset + 1, text, message)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.line.as_bytes().get(self.offset).copied()
+    }
+
+    fn packet(&mut self) -> Result<Packet> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(b'0'..=b'9') => self.integer(),
+            _ => Err(self.error("expected a list or an integer")),
+        }
+    }
+
+    fn integer(&mut self) -> Result<Packet> {
+        let start = self.offset;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.offset += 1;
+        }
+        let digits = &self.line[start..self.offset];
+        digits.parse::<u32>().map(Packet::Integer).map_err(|_| {
+            Error::parse(
+                self.line_number,
+                start + 1,
+                digits,
+                "expected a smaller integer",
+            )
+        })
+    }
+
+    fn list(&mut self) -> Result<Packet> {
+        if self.depth == MAX_DEPTH {
+            let message = format!("expected lists nested at most {} deep", MAX_DEPTH);
+            return Err(self.error(&message));
+        }
+        self.depth += 1;
+        self.offset += 1;
+        let mut items: Vec<Packet> = vec![];
+        if self.peek() == Some(b']') {
+            self.offset += 1;
+            self.depth -= 1;
+            return Ok(Packet::List(items));
+        }
+        loop {
+            items.push(self.packet()?);
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b']') => {
+                    self.offset += 1;
+                    self.depth -= 1;
+                    return Ok(Packet::List(items));
+                }
+                _ => return Err(self.error("expected , or ]")),
+            }
+        }
+    }
+}
+
+fn parse_packet(line_number: usize, line: &str) -> Result<Packet> {
+    let mut parser = PacketParser {
+        line_number,
+        line,
+        offset: 0,
+        depth: 0,
+    };
+    if parser.peek() != Some(b'[') {
+        return Err(parser.error("expected a packet list"));
+    }
+    let packet = parser.list()?;
+    match parser.offset == line.len() {
+        true => Ok(packet),
+        false => Err(parser.error("expected the end of the packet")),
+    }
+}
+
+// Pairs are separated by blank lines, and each must be exactly two packets.
+fn collect_pairs(input: &str) -> Result<Vec<Pair>> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, l)| (index + 1, l.trim_end()))
+        .collect::<Vec<(usize, &str)>>();
+    lines
+        .split(|(_, l)| l.trim().is_empty())
+        .filter(|group| !group.is_empty())
+        .map(|group| match group {
+            [(left_line, left), (right_line, right)] => Ok((
+                parse_packet(*left_line, left)?,
+                parse_packet(*right_line, right)?,
+            )),
+            _ => {
+                let (line_number, l) = group[group.len().min(3) - 1];
+                Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected a pair of packets between blank lines",
+                ))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_pairs() -> Vec<Pair> {
+        collect_pairs(&read_fixture(13, "test.txt")).unwrap()
+    }
+
+    fn packet(line: &str) -> Packet {
+        parse_packet(1, line).unwrap()
+    }
+
+    #[test]
+    fn test_parse_packet() {
+        assert_eq!(
+            packet("[[1],[2,3,4]]"),
+            Packet::List(vec![
+                Packet::List(vec![Packet::Integer(1)]),
+                Packet::List(vec![
+                    Packet::Integer(2),
+                    Packet::Integer(3),
+                    Packet::Integer(4)
+                ]),
+            ])
+        );
+        assert_eq!(packet("[]"), Packet::List(vec![]));
+        assert_eq!(packet("[10,[[]]]").to_string(), "[10,[[]]]");
+    }
+
+    #[test]
+    fn test_parse_packet_reports_bad_input() {
+        let cases = [
+            ("[1,2", Error::parse(1, 5, "", "expected , or ]")),
+            (
+                "[1,,2]",
+                Error::parse(1, 4, ",", "expected a list or an integer"),
+            ),
+            (
+                "[1]]",
+                Error::parse(1, 4, "]", "expected the end of the packet"),
+            ),
+            ("7", Error::parse(1, 1, "7", "expected a packet list")),
+            ("[1;2]", Error::parse(1, 3, ";", "expected , or ]")),
+        ];
+        cases.into_iter().for_each(|(line, error)| {
+            assert_eq!(parse_packet(1, line), Err(error));
+        });
+    }
+
+    #[test]
+    fn test_parse_packet_limits_nesting() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse_packet(1, &nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            parse_packet(1, &nested(200_000)),
+            Err(Error::parse(
+                1,
+                MAX_DEPTH + 1,
+                "[",
+                "expected lists nested at most 100 deep"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_collect_pairs_reports_bad_groups() {
+        let cases = [
+            ("[1]\n[2]\n[3]\n\n[4]\n[5]\n", (3, "[3]")),
+            ("[1]\n[2]\n\n[3]\n", (4, "[3]")),
+        ];
+        cases.into_iter().for_each(|(input, (line_number, l))| {
+            assert_eq!(
+                collect_pairs(input),
+                Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected a pair of packets between blank lines"
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn test_packet_order() {
+        let pairs = fixture_pairs();
+        let expecteds = vec![true, true, false, true, false, true, false, false];
+        TestCase::create_many(pairs, expecteds)
+            .iter()
+            .for_each(|case| {
+                let (left, right) = &case.input;
+                assert_eq!(left < right, case.expected);
+            });
+    }
+
+    #[test]
+    fn test_sum_ordered_pair_indices() {
+        assert_eq!(sum_ordered_pair_indices(&fixture_pairs()), 13);
+    }
+
+    #[test]
+    fn test_decoder_key() {
+        let pairs = fixture_pairs();
+        assert_eq!(decoder_key(&pairs), 140);
+        let mut packets = pairs
+            .into_iter()
+            .flat_map(|(left, right)| [left, right])
+            .collect::<Vec<Packet>>();
+        packets.extend([divider(2), divider(6)]);
+        packets.sort();
+        assert_eq!(packets[9], divider(2));
+        assert_eq!(packets[13], divider(6));
+    }
+}
@@ -0,0 +1,370 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines, parse_field};
+use crate::parts::{OptionSpec, Params, Selector};
+use crate::solver::Solver;
+
+pub const SAND_SOURCE: Point = (500, 0);
+
+/// The largest x or y coordinate accepted, which keeps the cave to a few million
+/// tiles.
+pub const MAX_COORDINATE: usize = 1000;
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+    const VARIANTS: &'static [OptionSpec] = &[OptionSpec {
+        name: "render",
+        about: "Draw the cave as ASCII once the sand of part <n> (1 or 2) comes to rest",
+    }];
+
+    type Input<'a> = Vec<RockPath>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_rock_paths(input)
+    }
+
+    fn part_one(rock_paths: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut cave = Cave::new(rock_paths, Bottom::Abyss);
+        Ok(Answer::from(cave.pour_sand()))
+    }
+
+    fn part_two(rock_paths: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let mut cave = Cave::new(rock_paths, Bottom::Floor);
+        Ok(Answer::from(cave.pour_sand()))
+    }
+
+    fn variant(rock_paths: &Self::Input<'_>, name: &str, params: &Params) -> Result<Answer> {
+        if name != "render" {
+            return Err(Error::Usage(format!("Day 14 has no variant {}", name)));
+        }
+        let bottom = match params.require::<u8>("render")? {
+            1 => Bottom::Abyss,
+            2 => Bottom::Floor,
+            other => {
+                return Err(Error::Usage(format!(
+                    "Invalid value {} for --render",
+                    other
+                )))
+            }
+        };
+        let mut cave = Cave::new(rock_paths, bottom);
+        cave.pour_sand();
+        Ok(Answer::Grid(cave.render()))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day14::run(selector, input)
+}
+
+pub type Point = (usize, usize);
+
+/// The corners of one rock structure, joined by horizontal and vertical lines.
+pub type RockPath = Vec<Point>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bottom {
+    /// Sand falling below the lowest rock falls forever.
+    Abyss,
+    /// An endless floor two below the lowest rock catches the sand.
+    Floor,
+}
+
+/// A cave whose x coordinates are all shifted right by `shift`, so that sand piling
+/// up left of x=0 still has room.
+struct Cave {
+    tiles: Vec<Vec<Tile>>,
+    /// The shifted x coordinate of the first column in `tiles`.
+    left: usize,
+    shift: usize,
+    floor: usize,
+    bottom: Bottom,
+}
+
+impl Cave {
+    // Sand resting on the floor forms a pile no wider than its height either side
+    // of the source, so that is as wide as the cave needs to be.
+    fn new(rock_paths: &[RockPath], bottom: Bottom) -> Self {
+        let lowest = rock_paths
+            .iter()
+            .flatten()
+            .map(|&(_, y)| y)
+            .max()
+            .unwrap_or(0);
+        let floor = lowest + 2;
+        let shift = floor + 1;
+        let left = rock_paths
+            .iter()
+            .flatten()
+            .map(|&(x, _)| x + shift)
+            .chain([SAND_SOURCE.0])
+            .min()
+            .unwrap_or(0);
+        let right = rock_paths
+            .iter()
+            .flatten()
+            .map(|&(x, _)| x + shift)
+            .chain([SAND_SOURCE.0 + 2 * shift])
+            .max()
+            .unwrap_or(0);
+        let mut cave = Cave {
+            tiles: vec![vec![Tile::Air; right - left + 1]; floor],
+            left,
+            shift,
+            floor,
+            bottom,
+        };
+        for path in rock_paths {
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                for y in y1.min(y2)..=y1.max(y2) {
+                    for x in x1.min(x2)..=x1.max(x2) {
+                        cave.tiles[y][x + shift - left] = Tile::Rock;
+                    }
+                }
+            }
+            if let [(x, y)] = path[..] {
+                cave.tiles[y][x + shift - left] = Tile::Rock;
+            }
+        }
+        cave
+    }
+
+    fn source(&self) -> Point {
+        (SAND_SOURCE.0 + self.shift, SAND_SOURCE.1)
+    }
+
+    fn is_open(&self, (x, y): Point) -> bool {
+        y < self.floor && self.tiles[y][x - self.left] == Tile::Air
+    }
+
+    /// Where a unit of sand from the source comes to rest, or `None` if it falls into
+    /// the abyss or the source is already blocked. `path` is the route the previous
+    /// unit fell by, which this one follows for as long as it is still open.
+    fn drop_sand(&self, path: &mut Vec<Point>) -> Option<Point> {
+        while path.last().is_some_and(|&point| !self.is_open(point)) {
+            path.pop();
+        }
+        let &(mut x, mut y) = path.last()?;
+        loop {
+            if y + 1 == self.floor {
+                return match self.bottom {
+                    Bottom::Abyss => None,
+                    Bottom::Floor => Some((x, y)),
+                };
+            }
+            match [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                .into_iter()
+                .find(|&point| self.is_open(point))
+            {
+                Some(next) => {
+                    path.push(next);
+                    (x, y) = next;
+                }
+                None => return Some((x, y)),
+            }
+        }
+    }
+
+    /// Pours sand until no more comes to rest, returning how many units did.
+    fn pour_sand(&mut self) -> usize {
+        let mut units = 0;
+        let mut path = vec![self.source()];
+        while let Some((x, y)) = self.drop_sand(&mut path) {
+            self.tiles[y][x - self.left] = Tile::Sand;
+            units += 1;
+        }
+        units
+    }
+
+    /// The part of the cave that holds rock or sand, with the source marked `+`.
+    fn render(&self) -> Vec<String> {
+        let occupied = |x: usize| self.tiles.iter().any(|row| row[x - self.left] != Tile::Air);
+        let source = self.source();
+        let columns = self.left..self.left + self.tiles[0].len();
+        let left = columns.clone().find(|&x| occupied(x)).unwrap_or(source.0);
+        let right = columns.rev().find(|&x| occupied(x)).unwrap_or(source.0);
+        let (left, right) = (left.min(source.0), right.max(source.0));
+        let mut rows = self
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                (left..=right)
+                    .map(|x| match row[x - self.left] {
+                        _ if (x, y) == source && row[x - self.left] == Tile::Air => '+',
+                        Tile::Air => '.',
+                        Tile::Rock => '#',
+                        Tile::Sand => 'o',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        if self.bottom == Bottom::Floor {
+            rows.push("#".repeat(right - left + 1));
+        }
+        rows
+    }
+}
+
+fn parse_point(line_number: usize, line: &str, field: &str) -> Result<Point> {
+    let (x, y) = field.trim().split_once(',').ok_or_else(|| {
+        let column = column_of(line, field.trim());
+        Error::parse(
+            line_number,
+            column,
+            field.trim(),
+            "expected a point like 498,4",
+        )
+    })?;
+    let coordinate = |field: &str, expected: &str| {
+        let value = parse_field(line_number, line, field, expected)?;
+        if value > MAX_COORDINATE {
+            let column = column_of(line, field);
+            let message = format!("{} up to {}", expected, MAX_COORDINATE);
+            return Err(Error::parse(line_number, column, field, &message));
+        }
+        Ok(value)
+    };
+    Ok((
+        coordinate(x, "expected an x coordinate")?,
+        coordinate(y, "expected a y coordinate")?,
+    ))
+}
+
+fn collect_rock_paths(input: &str) -> Result<Vec<RockPath>> {
+    numbered_lines(input)
+        .map(|(line_number, l)| {
+            let path = l
+                .split("->")
+                .map(|field| parse_point(line_number, l, field))
+                .collect::<Result<RockPath>>()?;
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                if x1 != x2 && y1 != y2 {
+                    let message = "expected only horizontal and vertical lines";
+                    return Err(Error::parse(line_number, 1, l, message));
+                }
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parts::Part;
+    use crate::test_helpers::read_fixture;
+
+    fn fixture_rock_paths() -> Vec<RockPath> {
+        collect_rock_paths(&read_fixture(14, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_rock_paths() {
+        let expected = vec![
+            vec![(498, 4), (498, 6), (496, 6)],
+            vec![(503, 4), (502, 4), (502, 9), (494, 9)],
+        ];
+        assert_eq!(fixture_rock_paths(), expected);
+    }
+
+    #[test]
+    fn test_collect_rock_paths_reports_bad_lines() {
+        assert_eq!(
+            collect_rock_paths("498,4 -> 498,x\n"),
+            Err(Error::parse(1, 14, "x", "expected a y coordinate"))
+        );
+        assert_eq!(
+            collect_rock_paths("1000000000,5 -> 1000000000,6\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "1000000000",
+                "expected an x coordinate up to 1000"
+            ))
+        );
+        assert_eq!(
+            collect_rock_paths("498,4 -> 4986\n"),
+            Err(Error::parse(1, 10, "4986", "expected a point like 498,4"))
+        );
+        assert_eq!(
+            collect_rock_paths("498,4 -> 500,6\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "498,4 -> 500,6",
+                "expected only horizontal and vertical lines"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_pour_sand() {
+        let rock_paths = fixture_rock_paths();
+        assert_eq!(Cave::new(&rock_paths, Bottom::Abyss).pour_sand(), 24);
+        assert_eq!(Cave::new(&rock_paths, Bottom::Floor).pour_sand(), 93);
+    }
+
+    #[test]
+    fn test_pour_sand_past_the_left_edge() {
+        // The pile on the floor spreads further left than x=0.
+        let rock_paths = vec![vec![(500, 600), (501, 600)]];
+        assert_eq!(Cave::new(&rock_paths, Bottom::Abyss).pour_sand(), 0);
+        assert_eq!(Cave::new(&rock_paths, Bottom::Floor).pour_sand(), 362_402);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut cave = Cave::new(&fixture_rock_paths(), Bottom::Abyss);
+        cave.pour_sand();
+        let expected = vec![
+            "......+...",
+            "..........",
+            "......o...",
+            ".....ooo..",
+            "....#ooo##",
+            "...o#ooo#.",
+            "..###ooo#.",
+            "....oooo#.",
+            ".o.ooooo#.",
+            "#########.",
+            "..........",
+        ];
+        assert_eq!(cave.render(), expected);
+    }
+
+    #[test]
+    fn test_render_variant() {
+        let input = read_fixture(14, "test.txt");
+        let render = |n: &str| {
+            Selector::new(
+                Part::Variant(String::from("render")),
+                Params::default().with("render", n),
+            )
+        };
+        match run(&render("2"), &input) {
+            Ok(Answer::Grid(rows)) => {
+                assert_eq!(rows[0], "..........o..........");
+                assert_eq!(rows[rows.len() - 1], "#".repeat(21));
+            }
+            other => panic!("expected a grid, got {:?}", other),
+        }
+        assert_eq!(
+            run(&render("3"), &input),
+            Err(Error::Usage(String::from("Invalid value 3 for --render")))
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{numbered_lines, parse_field};
+use crate::parts::{OptionSpec, Params, Selector};
+use crate::solver::Solver;
+use std::collections::HashSet;
+
+pub const DEFAULT_ROW: i64 = 2_000_000;
+pub const DEFAULT_SEARCH_SIZE: i64 = 4_000_000;
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+    const PARAMS: &'static [OptionSpec] = &[
+        OptionSpec {
+            name: "row",
+            about: "Row that part 1 checks (default 2000000; 10 for the example)",
+        },
+        OptionSpec {
+            name: "size",
+            about: "Part 2 searches x and y from 0 to <n> (default 4000000; 20 for the example)",
+        },
+    ];
+
+    type Input<'a> = Vec<Sensor>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_sensors(input)
+    }
+
+    fn part_one(sensors: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let row = params.get_or("row", DEFAULT_ROW)?;
+        Ok(Answer::from(count_excluded_positions(sensors, row)))
+    }
+
+    fn part_two(sensors: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let size = params.get_or("size", DEFAULT_SEARCH_SIZE)?;
+        let beacon = find_distress_beacon(sensors, size);
+        Ok(Answer::from(beacon.map(|(x, y)| x * 4_000_000 + y)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day15::run(selector, input)
+}
+
+type Point = (i64, i64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sensor {
+    position: Point,
+    beacon: Point,
+}
+
+impl Sensor {
+    fn radius(&self) -> i64 {
+        distance(self.position, self.beacon)
+    }
+
+    fn covers(&self, point: Point) -> bool {
+        distance(self.position, point) <= self.radius()
+    }
+
+    /// The inclusive range of x positions this sensor rules out on a row, if any.
+    fn coverage_on_row(&self, row: i64) -> Option<(i64, i64)> {
+        let reach = self.radius() - (self.position.1 - row).abs();
+        match reach >= 0 {
+            true => Some((self.position.0 - reach, self.position.0 + reach)),
+            false => None,
+        }
+    }
+}
+
+fn distance(a: Point, b: Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Sorts and merges overlapping or adjacent inclusive ranges.
+fn merge_intervals(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    intervals.sort();
+    let mut merged: Vec<(i64, i64)> = vec![];
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+// Beacons already known to be on the row are not counted as excluded positions.
+fn count_excluded_positions(sensors: &[Sensor], row: i64) -> i64 {
+    let intervals = sensors
+        .iter()
+        .filter_map(|sensor| sensor.coverage_on_row(row))
+        .collect();
+    let merged = merge_intervals(intervals);
+    let covered = merged
+        .iter()
+        .map(|(start, end)| end - start + 1)
+        .sum::<i64>();
+    let beacons = sensors
+        .iter()
+        .map(|sensor| sensor.beacon)
+        .filter(|&(x, y)| y == row && merged.iter().any(|&(start, end)| start <= x && x <= end))
+        .collect::<HashSet<Point>>();
+    covered - beacons.len() as i64
+}
+
+// A single uncovered point must sit just outside some sensors' ranges. Those edges
+// lie on diagonal lines, so the point is where such lines cross, or else a corner
+// of the search square.
+fn find_distress_beacon(sensors: &[Sensor], size: i64) -> Option<Point> {
+    let mut rising: HashSet<i64> = HashSet::new();
+    let mut falling: HashSet<i64> = HashSet::new();
+    for sensor in sensors {
+        let ((x, y), edge) = (sensor.position, sensor.radius() + 1);
+        rising.extend([y - x - edge, y - x + edge]);
+        falling.extend([y + x - edge, y + x + edge]);
+    }
+    let crossings = rising.iter().flat_map(|&a| {
+        falling
+            .iter()
+            .filter(move |&&b| (b - a) % 2 == 0)
+            .map(move |&b| ((b - a) / 2, (a + b) / 2))
+    });
+    let corners = [(0, 0), (0, size), (size, 0), (size, size)];
+    crossings.chain(corners).find(|&(x, y)| {
+        (0..=size).contains(&x)
+            && (0..=size).contains(&y)
+            && !sensors.iter().any(|sensor| sensor.covers((x, y)))
+    })
+}
+
+fn parse_sensor(line_number: usize, l: &str) -> Result<Sensor> {
+    let malformed = || {
+        let message = "expected `Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>`";
+        Error::parse(line_number, 1, l, message)
+    };
+    let rest = l
+        .trim()
+        .strip_prefix("Sensor at x=")
+        .ok_or_else(malformed)?;
+    let (sensor_x, rest) = rest.split_once(", y=").ok_or_else(malformed)?;
+    let (sensor_y, rest) = rest
+        .split_once(": closest beacon is at x=")
+        .ok_or_else(malformed)?;
+    let (beacon_x, beacon_y) = rest.split_once(", y=").ok_or_else(malformed)?;
+    let coordinate = |field| parse_field(line_number, l, field, "expected a coordinate");
+    Ok(Sensor {
+        position: (coordinate(sensor_x)?, coordinate(sensor_y)?),
+        beacon: (coordinate(beacon_x)?, coordinate(beacon_y)?),
+    })
+}
+
+fn collect_sensors(input: &str) -> Result<Vec<Sensor>> {
+    numbered_lines(input)
+        .map(|(line_number, l)| parse_sensor(line_number, l))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parts::Part;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_sensors() -> Vec<Sensor> {
+        collect_sensors(&read_fixture(15, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_sensors() {
+        let sensors = fixture_sensors();
+        assert_eq!(sensors.len(), 14);
+        assert_eq!(
+            sensors[0],
+            Sensor {
+                position: (2, 18),
+                beacon: (-2, 15)
+            }
+        );
+        assert_eq!(
+            collect_sensors("Sensor at x=2, y=18: closest beacon is at x=-2, y=1x5\n"),
+            Err(Error::parse(1, 51, "1x5", "expected a coordinate"))
+        );
+    }
+
+    #[test]
+    fn test_merge_intervals() {
+        let inputs = vec![
+            vec![(12, 12), (2, 14), (16, 24), (14, 18), (-2, 2)],
+            vec![(0, 3), (5, 6)],
+            vec![(0, 3), (4, 6)],
+        ];
+        let expecteds = vec![vec![(-2, 24)], vec![(0, 3), (5, 6)], vec![(0, 6)]];
+        TestCase::create_many(inputs, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(merge_intervals(case.input.clone()), case.expected);
+            });
+    }
+
+    #[test]
+    fn test_count_excluded_positions() {
+        assert_eq!(count_excluded_positions(&fixture_sensors(), 10), 26);
+    }
+
+    #[test]
+    fn test_find_distress_beacon() {
+        assert_eq!(find_distress_beacon(&fixture_sensors(), 20), Some((14, 11)));
+    }
+
+    #[test]
+    fn test_params() {
+        let input = read_fixture(15, "test.txt");
+        let row = Selector::new(Part::One, Params::default().with("row", "10"));
+        let size = Selector::new(Part::Two, Params::default().with("size", "20"));
+        assert_eq!(run(&row, &input), Ok(Answer::from(26)));
+        assert_eq!(run(&size, &input), Ok(Answer::from(56000011i64)));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::collections::HashMap;
+
+pub const START_VALVE: &str = "AA";
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+
+    type Input<'a> = Network;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_network(input)
+    }
+
+    fn part_one(network: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let best = network.best_by_valve_set(30);
+        Ok(Answer::from(best.values().max().copied().map(u64::from)))
+    }
+
+    fn part_two(network: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let best = network.best_by_valve_set(26);
+        Ok(Answer::from(best_with_elephant(&best).map(u64::from)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day16::run(selector, input)
+}
+
+#[derive(Debug, PartialEq)]
+struct Valve<'a> {
+    name: &'a str,
+    flow_rate: u32,
+    tunnels: Vec<&'a str>,
+}
+
+/// The valves worth opening, with the travel time between each pair of them.
+#[derive(Debug, PartialEq)]
+pub struct Network {
+    flow_rates: Vec<u32>,
+    /// Minutes from one useful valve to another.
+    distances: Vec<Vec<u32>>,
+    /// Minutes from the start valve to each useful valve.
+    from_start: Vec<u32>,
+}
+
+impl Network {
+    // Valves with no flow are only ever passed through, so the graph is reduced to
+    // the shortest paths (by Floyd–Warshall) between the start and useful valves.
+    fn new(valves: &[Valve]) -> Self {
+        let index = valves
+            .iter()
+            .enumerate()
+            .map(|(index, valve)| (valve.name, index))
+            .collect::<HashMap<&str, usize>>();
+        let unreachable = u32::MAX / 2;
+        let mut distances = vec![vec![unreachable; valves.len()]; valves.len()];
+        for (from, valve) in valves.iter().enumerate() {
+            distances[from][from] = 0;
+            for tunnel in &valve.tunnels {
+                distances[from][index[tunnel]] = 1;
+            }
+        }
+        for via in 0..valves.len() {
+            for from in 0..valves.len() {
+                for to in 0..valves.len() {
+                    let distance = distances[from][via] + distances[via][to];
+                    if distance < distances[from][to] {
+                        distances[from][to] = distance;
+                    }
+                }
+            }
+        }
+        let useful = (0..valves.len())
+            .filter(|&index| valves[index].flow_rate > 0)
+            .collect::<Vec<usize>>();
+        Network {
+            flow_rates: useful
+                .iter()
+                .map(|&index| valves[index].flow_rate)
+                .collect(),
+            distances: useful
+                .iter()
+                .map(|&from| useful.iter().map(|&to| distances[from][to]).collect())
+                .collect(),
+            from_start: useful
+                .iter()
+                .map(|&to| distances[index[START_VALVE]][to])
+                .collect(),
+        }
+    }
+
+    /// The most pressure that can be released within `minutes` by opening exactly
+    /// each set of valves (as a bit mask) that can be opened in that time.
+    fn best_by_valve_set(&self, minutes: u32) -> HashMap<u64, u32> {
+        let mut best: HashMap<u64, u32> = HashMap::from([(0, 0)]);
+        for valve in 0..self.flow_rates.len() {
+            self.open_valves(valve, self.from_start[valve], minutes, 0, 0, &mut best);
+        }
+        best
+    }
+
+    // Moves to `valve` and opens it, then tries every valve still closed next.
+    fn open_valves(
+        &self,
+        valve: usize,
+        distance: u32,
+        minutes_left: u32,
+        opened: u64,
+        released: u32,
+        best: &mut HashMap<u64, u32>,
+    ) {
+        if distance + 1 >= minutes_left {
+            return;
+        }
+        let minutes_left = minutes_left - distance - 1;
+        let opened = opened | 1 << valve;
+        let released = released + self.flow_rates[valve] * minutes_left;
+        let entry = best.entry(opened).or_insert(0);
+        *entry = (*entry).max(released);
+        for next in (0..self.flow_rates.len()).filter(|next| opened & 1 << next == 0) {
+            let distance = self.distances[valve][next];
+            self.open_valves(next, distance, minutes_left, opened, released, best);
+        }
+    }
+}
+
+// Every set reached by opening valves in some order is recorded, so the best
+// split is the best pair of disjoint sets, one for you and one for the elephant.
+fn best_with_elephant(best: &HashMap<u64, u32>) -> Option<u32> {
+    let mut sets = best.iter().collect::<Vec<(&u64, &u32)>>();
+    sets.sort_by(|a, b| b.1.cmp(a.1));
+    let mut most: Option<u32> = None;
+    for (index, &(&mine, &released)) in sets.iter().enumerate() {
+        if most.is_some_and(|most| released * 2 < most) {
+            break;
+        }
+        if let Some((_, &other)) = sets[index..].iter().find(|(&theirs, _)| mine & theirs == 0) {
+            most = most.max(Some(released + other));
+        }
+    }
+    most
+}
+
+fn parse_valve(line_number: usize, l: &str) -> Result<Valve<'_>> {
+    let malformed = || {
+        let message = "expected a valve name, its flow rate and the valves its tunnels lead to";
+        Error::parse(line_number, 1, l, message)
+    };
+    let rest = l.trim().strip_prefix("Valve ").ok_or_else(malformed)?;
+    let (name, rest) = rest.split_once(" has flow rate=").ok_or_else(malformed)?;
+    let (flow_rate, rest) = rest.split_once("; ").ok_or_else(malformed)?;
+    let tunnels = rest
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| rest.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(malformed)?;
+    Ok(Valve {
+        name,
+        flow_rate: parse_field(line_number, l, flow_rate, "expected a flow rate")?,
+        tunnels: tunnels.split(", ").map(str::trim).collect(),
+    })
+}
+
+fn collect_network(input: &str) -> Result<Network> {
+    let lines = numbered_lines(input).collect::<Vec<(usize, &str)>>();
+    let valves = lines
+        .iter()
+        .map(|&(line_number, l)| parse_valve(line_number, l))
+        .collect::<Result<Vec<Valve>>>()?;
+    for (&(line_number, l), valve) in lines.iter().zip(&valves) {
+        let unknown = valve
+            .tunnels
+            .iter()
+            .find(|&&tunnel| !valves.iter().any(|other| other.name == tunnel));
+        if let Some(tunnel) = unknown {
+            let column = column_of(l, tunnel);
+            return Err(Error::parse(
+                line_number,
+                column,
+                tunnel,
+                "expected a known valve",
+            ));
+        }
+    }
+    let (line_number, l) = lines.last().copied().unwrap_or((0, ""));
+    if !valves.iter().any(|valve| valve.name == START_VALVE) {
+        let message = format!("expected a valve named {}", START_VALVE);
+        return Err(Error::parse(line_number, 1, l, &message));
+    }
+    let useful = valves.iter().filter(|valve| valve.flow_rate > 0).count();
+    if useful > 64 {
+        let message = "expected at most 64 valves with a flow rate";
+        return Err(Error::parse(line_number, 1, l, message));
+    }
+    Ok(Network::new(&valves))
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::read_fixture;
+
+    fn fixture_network() -> Network {
+        collect_network(&read_fixture(16, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_parse_valve() {
+        assert_eq!(
+            parse_valve(
+                1,
+                "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB"
+            ),
+            Ok(Valve {
+                name: "AA",
+                flow_rate: 0,
+                tunnels: vec!["DD", "II", "BB"],
+            })
+        );
+        assert_eq!(
+            parse_valve(1, "Valve HH has flow rate=22; tunnel leads to valve GG"),
+            Ok(Valve {
+                name: "HH",
+                flow_rate: 22,
+                tunnels: vec!["GG"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_collect_network_reports_bad_lines() {
+        assert_eq!(
+            collect_network("Valve AA has flow rate=x; tunnel leads to valve AA\n"),
+            Err(Error::parse(1, 24, "x", "expected a flow rate"))
+        );
+        assert_eq!(
+            collect_network("Valve AA has flow rate=1; tunnel leads to valve BB\n"),
+            Err(Error::parse(1, 49, "BB", "expected a known valve"))
+        );
+        assert_eq!(
+            collect_network("Valve BB has flow rate=1; tunnel leads to valve BB\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "Valve BB has flow rate=1; tunnel leads to valve BB",
+                "expected a valve named AA"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_network() {
+        let network = fixture_network();
+        assert_eq!(network.flow_rates, vec![13, 2, 20, 3, 22, 21]);
+        assert_eq!(network.from_start, vec![1, 2, 1, 2, 5, 2]);
+        assert_eq!(network.distances[4][5], 7);
+    }
+
+    #[test]
+    fn test_most_pressure() {
+        let network = fixture_network();
+        assert_eq!(network.best_by_valve_set(30).values().max(), Some(&1651));
+        assert_eq!(
+            best_with_elephant(&network.best_by_valve_set(26)),
+            Some(1707)
+        );
+    }
+}
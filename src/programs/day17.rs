@@ -0,0 +1,233 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::numbered_lines;
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::collections::HashMap;
+
+pub const CHAMBER_WIDTH: usize = 7;
+
+/// How far below the top of the tower the surface is compared when looking for a
+/// repeat; rock deeper than this no longer affects where new rocks come to rest.
+const SURFACE_DEPTH: usize = 64;
+
+/// The rock shapes in the order they fall, as rows from the bottom up. Bit 6 is the
+/// leftmost column of the chamber and bit 0 the rightmost.
+const ROCKS: [&[u8]; 5] = [
+    &[0b1111000],
+    &[0b0100000, 0b1110000, 0b0100000],
+    &[0b1110000, 0b0010000, 0b0010000],
+    &[0b1000000, 0b1000000, 0b1000000, 0b1000000],
+    &[0b1100000, 0b1100000],
+];
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Pyroclastic Flow";
+
+    type Input<'a> = Vec<Jet>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_jets(input)
+    }
+
+    fn part_one(jets: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(tower_height(jets, 2022)))
+    }
+
+    fn part_two(jets: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(tower_height(jets, 1_000_000_000_000)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day17::run(selector, input)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// What the next rock will meet: which rock and jet come next, and how far down
+/// each column's highest rock is from the top of the tower (at most
+/// `SURFACE_DEPTH`, which also stands for a column with no rock near the top).
+type State = (usize, usize, [usize; CHAMBER_WIDTH]);
+
+#[derive(Default)]
+struct Chamber {
+    /// Settled rock, one bit per column, from the floor up.
+    rows: Vec<u8>,
+    rocks: u64,
+    jet: usize,
+}
+
+impl Chamber {
+    fn collides(&self, rock: &[u8], bottom: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(offset, row)| self.rows.get(bottom + offset).is_some_and(|r| r & row != 0))
+    }
+
+    // A rock appears two columns from the left wall and three rows above the tower,
+    // then alternately gets pushed by a jet and falls one row until it cannot fall.
+    fn drop_rock(&mut self, jets: &[Jet]) {
+        let mut rock = ROCKS[self.rocks as usize % ROCKS.len()]
+            .iter()
+            .map(|row| row >> 2)
+            .collect::<Vec<u8>>();
+        let mut bottom = self.rows.len() + 3;
+        loop {
+            let pushed = match jets[self.jet] {
+                Jet::Left if rock.iter().all(|row| row & 0b1000000 == 0) => {
+                    Some(rock.iter().map(|row| row << 1).collect::<Vec<u8>>())
+                }
+                Jet::Right if rock.iter().all(|row| row & 1 == 0) => {
+                    Some(rock.iter().map(|row| row >> 1).collect::<Vec<u8>>())
+                }
+                _ => None,
+            };
+            self.jet = (self.jet + 1) % jets.len();
+            if let Some(pushed) = pushed.filter(|pushed| !self.collides(pushed, bottom)) {
+                rock = pushed;
+            }
+            if bottom == 0 || self.collides(&rock, bottom - 1) {
+                break;
+            }
+            bottom -= 1;
+        }
+        for (offset, row) in rock.iter().enumerate() {
+            if bottom + offset == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[bottom + offset] |= row;
+        }
+        self.rocks += 1;
+    }
+
+    fn height(&self) -> u64 {
+        self.rows.len() as u64
+    }
+
+    fn state(&self) -> State {
+        let mut depths = [SURFACE_DEPTH; CHAMBER_WIDTH];
+        for (depth, row) in self.rows.iter().rev().take(SURFACE_DEPTH).enumerate() {
+            for (column, column_depth) in depths.iter_mut().enumerate() {
+                if row & (0b1000000 >> column) != 0 && *column_depth == SURFACE_DEPTH {
+                    *column_depth = depth;
+                }
+            }
+        }
+        (self.rocks as usize % ROCKS.len(), self.jet, depths)
+    }
+}
+
+// Once the chamber is in a state it has been in before, the rocks since then
+// repeat for as long as needed, so whole repeats are skipped over and only the
+// rocks after the last one are simulated.
+fn tower_height(jets: &[Jet], rocks: u64) -> u64 {
+    let mut chamber = Chamber::default();
+    let mut seen: HashMap<State, (u64, u64)> = HashMap::new();
+    let mut skipped: Option<u64> = None;
+    while chamber.rocks < rocks {
+        chamber.drop_rock(jets);
+        if skipped.is_none() {
+            let (count, height) = (chamber.rocks, chamber.height());
+            if let Some((before, height_before)) = seen.insert(chamber.state(), (count, height)) {
+                let repeats = (rocks - count) / (count - before);
+                chamber.rocks += repeats * (count - before);
+                skipped = Some(repeats * (height - height_before));
+            }
+        }
+    }
+    chamber.height() + skipped.unwrap_or(0)
+}
+
+fn collect_jets(input: &str) -> Result<Vec<Jet>> {
+    let mut jets: Vec<Jet> = vec![];
+    for (line_number, l) in numbered_lines(input) {
+        for (offset, c) in l.trim().char_indices() {
+            match c {
+                '<' => jets.push(Jet::Left),
+                '>' => jets.push(Jet::Right),
+                _ => {
+                    let text = &l.trim()[offset..offset + c.len_utf8()];
+                    let column = l.trim()[..offset].chars().count() + 1;
+                    return Err(Error::parse(line_number, column, text, "expected < or >"));
+                }
+            }
+        }
+    }
+    match jets.is_empty() {
+        true => Err(Error::parse(1, 1, "", "expected a jet pattern")),
+        false => Ok(jets),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_jets() -> Vec<Jet> {
+        collect_jets(&read_fixture(17, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_jets() {
+        let jets = fixture_jets();
+        assert_eq!(jets.len(), 40);
+        assert_eq!(jets[..4], [Jet::Right, Jet::Right, Jet::Right, Jet::Left]);
+        assert_eq!(
+            collect_jets(">><x<\n"),
+            Err(Error::parse(1, 4, "x", "expected < or >"))
+        );
+        assert_eq!(
+            collect_jets("\n"),
+            Err(Error::parse(1, 1, "", "expected a jet pattern"))
+        );
+    }
+
+    #[test]
+    fn test_drop_rock() {
+        let jets = fixture_jets();
+        let mut chamber = Chamber::default();
+        for _ in 0..3 {
+            chamber.drop_rock(&jets);
+        }
+        let expected: Vec<u8> = vec![
+            0b0011110, 0b0001000, 0b0011100, 0b1111000, 0b0010000, 0b0010000,
+        ];
+        assert_eq!(chamber.rows, expected);
+    }
+
+    #[test]
+    fn test_tower_height() {
+        let jets = fixture_jets();
+        let rocks = vec![1, 2, 10, 2022, 1_000_000_000_000];
+        let expecteds = vec![1, 4, 17, 3068, 1514285714288];
+        TestCase::create_many(rocks, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(tower_height(&jets, case.input), case.expected);
+            });
+    }
+
+    #[test]
+    fn test_tower_height_with_an_empty_column() {
+        // Pushed only left, no rock ever reaches the rightmost column, so the
+        // repeat has to be found without that column's depth.
+        let jets = vec![Jet::Left];
+        let mut chamber = Chamber::default();
+        for _ in 0..5000 {
+            chamber.drop_rock(&jets);
+        }
+        assert_eq!(tower_height(&jets, 5000), chamber.height());
+        assert_eq!(tower_height(&jets, 2022), 4448);
+        assert_eq!(tower_height(&jets, 1_000_000_000_000), 2_200_000_000_000);
+    }
+}
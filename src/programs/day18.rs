@@ -0,0 +1,158 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::collections::{HashSet, VecDeque};
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Boiling Boulders";
+
+    type Input<'a> = Vec<Cube>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_cubes(input)
+    }
+
+    fn part_one(cubes: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(surface_area(cubes)))
+    }
+
+    fn part_two(cubes: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(exterior_surface_area(cubes)))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day18::run(selector, input)
+}
+
+pub type Cube = (i32, i32, i32);
+
+fn neighbours((x, y, z): Cube) -> [Cube; 6] {
+    [
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+/// Faces not touching another cube, including those facing trapped air pockets.
+fn surface_area(cubes: &[Cube]) -> usize {
+    let droplet = cubes.iter().copied().collect::<HashSet<Cube>>();
+    droplet
+        .iter()
+        .flat_map(|&cube| neighbours(cube))
+        .filter(|neighbour| !droplet.contains(neighbour))
+        .count()
+}
+
+// Steam fills everything reachable from outside the droplet, so a flood fill of a
+// box one larger than the droplet on every side counts each exterior face once,
+// when the steam meets it.
+fn exterior_surface_area(cubes: &[Cube]) -> usize {
+    if cubes.is_empty() {
+        return 0;
+    }
+    let droplet = cubes.iter().copied().collect::<HashSet<Cube>>();
+    let (mut low, mut high) = ([i32::MAX; 3], [i32::MIN; 3]);
+    for &(x, y, z) in cubes {
+        for (axis, coordinate) in [x, y, z].into_iter().enumerate() {
+            low[axis] = low[axis].min(coordinate - 1);
+            high[axis] = high[axis].max(coordinate + 1);
+        }
+    }
+    let inside = |(x, y, z): Cube| {
+        [x, y, z]
+            .into_iter()
+            .enumerate()
+            .all(|(axis, coordinate)| (low[axis]..=high[axis]).contains(&coordinate))
+    };
+    let start = (low[0], low[1], low[2]);
+    let mut steam = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut faces = 0;
+    while let Some(cube) = queue.pop_front() {
+        for next in neighbours(cube).into_iter().filter(|&next| inside(next)) {
+            if droplet.contains(&next) {
+                faces += 1;
+            } else if steam.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    faces
+}
+
+fn collect_cubes(input: &str) -> Result<Vec<Cube>> {
+    numbered_lines(input)
+        .map(|(line_number, l)| {
+            let fields = l.split(',').collect::<Vec<&str>>();
+            if fields.len() != 3 {
+                return Err(Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected a cube like 2,2,2",
+                ));
+            }
+            let coordinate = |field| parse_field(line_number, l, field, "expected a coordinate");
+            Ok((
+                coordinate(fields[0])?,
+                coordinate(fields[1])?,
+                coordinate(fields[2])?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    fn fixture_cubes() -> Vec<Cube> {
+        collect_cubes(&read_fixture(18, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_cubes() {
+        let cubes = fixture_cubes();
+        assert_eq!(cubes.len(), 13);
+        assert_eq!(cubes[0], (2, 2, 2));
+        assert_eq!(
+            collect_cubes("1,2\n"),
+            Err(Error::parse(1, 1, "1,2", "expected a cube like 2,2,2"))
+        );
+        assert_eq!(
+            collect_cubes("1,2,z\n"),
+            Err(Error::parse(1, 5, "z", "expected a coordinate"))
+        );
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let inputs = vec![vec![(1, 1, 1), (2, 1, 1)], fixture_cubes()];
+        let expecteds = vec![10, 64];
+        TestCase::create_many(inputs, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(surface_area(&case.input), case.expected);
+            });
+    }
+
+    #[test]
+    fn test_exterior_surface_area() {
+        assert_eq!(exterior_surface_area(&fixture_cubes()), 58);
+        assert_eq!(exterior_surface_area(&[]), 0);
+        // The box around a long, thin droplet stays long and thin.
+        assert_eq!(exterior_surface_area(&[(0, 0, 0), (400, 0, 0)]), 12);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{numbered_lines, parse_field};
+use crate::parts::{OptionSpec, Params, Selector};
+use crate::solver::Solver;
+
+/// The longest `--minutes` accepted. The search grows quickly with each minute: at
+/// 40 minutes thirty blueprints take a couple of seconds, at 60 one takes several.
+pub const MAX_MINUTES: u32 = 40;
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Not Enough Minerals";
+    const PARAMS: &'static [OptionSpec] = &[
+        OptionSpec {
+            name: "minutes",
+            about: "Minutes to open geodes in, up to 40 (default 24 for part 1, 32 for part 2)",
+        },
+        OptionSpec {
+            name: "blueprints",
+            about: "How many blueprints part 2 uses, from the first (default 3)",
+        },
+    ];
+
+    type Input<'a> = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_blueprints(input)
+    }
+
+    fn part_one(blueprints: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let minutes = minutes_for(params, 24)?;
+        let quality_levels = blueprints
+            .iter()
+            .map(|blueprint| u64::from(blueprint.id) * u64::from(max_geodes(blueprint, minutes)))
+            .sum::<u64>();
+        Ok(Answer::from(quality_levels))
+    }
+
+    fn part_two(blueprints: &Self::Input<'_>, params: &Params) -> Result<Answer> {
+        let minutes = minutes_for(params, 32)?;
+        let count = params.get_or("blueprints", 3)?;
+        let geodes = blueprints
+            .iter()
+            .take(count)
+            .try_fold(1u64, |product, blueprint| {
+                product.checked_mul(u64::from(max_geodes(blueprint, minutes)))
+            })
+            .ok_or_else(|| {
+                Error::Failed(String::from("the product of geodes does not fit in a u64"))
+            })?;
+        Ok(Answer::from(geodes))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day19::run(selector, input)
+}
+
+fn minutes_for(params: &Params, default: u32) -> Result<u32> {
+    match params.get_or("minutes", default)? {
+        minutes if minutes <= MAX_MINUTES => Ok(minutes),
+        minutes => Err(Error::Usage(format!(
+            "Invalid value {} for --minutes",
+            minutes
+        ))),
+    }
+}
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Debug, PartialEq)]
+pub struct Blueprint {
+    id: u32,
+    /// What each kind of robot costs in ore, clay and obsidian, indexed by the
+    /// resource the robot collects.
+    costs: [[u32; 3]; 4],
+}
+
+impl Blueprint {
+    /// No more robots of a kind are needed than the most of that resource any one
+    /// robot costs, since only one robot can be built each minute.
+    fn max_robots(&self) -> [u32; 3] {
+        let mut most = [0; 3];
+        for cost in &self.costs {
+            for resource in [ORE, CLAY, OBSIDIAN] {
+                most[resource] = most[resource].max(cost[resource]);
+            }
+        }
+        most
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Inventory {
+    minutes_left: u32,
+    robots: [u32; 4],
+    resources: [u32; 4],
+}
+
+impl Inventory {
+    /// Waits until the robot is affordable and builds it, if that leaves time for it
+    /// to collect anything.
+    fn build(&self, cost: &[u32; 3], robot: usize) -> Option<Inventory> {
+        let mut wait = 0;
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            let missing = cost[resource].saturating_sub(self.resources[resource]);
+            if missing > 0 {
+                let robots = self.robots[resource];
+                if robots == 0 {
+                    return None;
+                }
+                wait = wait.max(missing.div_ceil(robots));
+            }
+        }
+        if wait + 1 >= self.minutes_left {
+            return None;
+        }
+        let mut next = *self;
+        next.minutes_left -= wait + 1;
+        for resource in [ORE, CLAY, OBSIDIAN, GEODE] {
+            next.resources[resource] += self.robots[resource] * (wait + 1);
+        }
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            next.resources[resource] -= cost[resource];
+        }
+        next.robots[robot] += 1;
+        Some(next)
+    }
+
+    fn geodes_if_idle(&self) -> u32 {
+        self.resources[GEODE] + self.robots[GEODE] * self.minutes_left
+    }
+
+    /// Whether there is already enough of `resource` to build the most expensive
+    /// robot every remaining minute, so more robots collecting it are no use.
+    fn has_enough(&self, resource: usize, most: u32) -> bool {
+        let minutes = self.minutes_left;
+        self.robots[resource] >= most
+            || self.resources[resource] + self.robots[resource] * minutes >= most * minutes
+    }
+
+    // If ore and clay were free and an obsidian robot came for nothing every minute,
+    // building a geode robot whenever the obsidian allowed would be best, so no
+    // real plan can open more geodes than that.
+    fn most_geodes(&self, obsidian_cost: u32) -> u32 {
+        let (mut obsidian, mut obsidian_robots) = (self.resources[OBSIDIAN], self.robots[OBSIDIAN]);
+        let (mut geodes, mut geode_robots) = (self.resources[GEODE], self.robots[GEODE]);
+        for _ in 0..self.minutes_left {
+            let build = obsidian >= obsidian_cost;
+            if build {
+                obsidian -= obsidian_cost;
+            }
+            obsidian += obsidian_robots;
+            geodes += geode_robots;
+            obsidian_robots += 1;
+            if build {
+                geode_robots += 1;
+            }
+        }
+        geodes
+    }
+}
+
+// Each step of the search picks the next robot to build and skips ahead to when it
+// is built. A branch is abandoned when even the most optimistic plan for the
+// remaining minutes could not beat the best found so far.
+fn search(blueprint: &Blueprint, max_robots: &[u32; 3], inventory: Inventory, best: &mut u32) {
+    *best = (*best).max(inventory.geodes_if_idle());
+    if inventory.most_geodes(blueprint.costs[GEODE][OBSIDIAN]) <= *best {
+        return;
+    }
+    for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+        if robot != GEODE && inventory.has_enough(robot, max_robots[robot]) {
+            continue;
+        }
+        if let Some(next) = inventory.build(&blueprint.costs[robot], robot) {
+            search(blueprint, max_robots, next, best);
+        }
+    }
+}
+
+fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let inventory = Inventory {
+        minutes_left: minutes,
+        robots: [1, 0, 0, 0],
+        resources: [0; 4],
+    };
+    let mut best = 0;
+    search(blueprint, &blueprint.max_robots(), inventory, &mut best);
+    best
+}
+
+const BLUEPRINT_TEXT: [&str; 8] = [
+    "Blueprint ",
+    ": Each ore robot costs ",
+    " ore. Each clay robot costs ",
+    " ore. Each obsidian robot costs ",
+    " ore and ",
+    " clay. Each geode robot costs ",
+    " ore and ",
+    " obsidian.",
+];
+
+fn parse_blueprint(line_number: usize, l: &str) -> Result<Blueprint> {
+    let malformed = |text: &str| {
+        let message = format!("expected `{}`", text.trim());
+        Error::parse(line_number, 1, l, &message)
+    };
+    let mut rest = l
+        .trim()
+        .strip_prefix(BLUEPRINT_TEXT[0])
+        .ok_or_else(|| malformed(BLUEPRINT_TEXT[0]))?;
+    let mut numbers: Vec<u32> = vec![];
+    for text in &BLUEPRINT_TEXT[1..] {
+        let (field, after) = rest.split_once(text).ok_or_else(|| malformed(text))?;
+        numbers.push(parse_field(line_number, l, field, "expected a number")?);
+        rest = after;
+    }
+    if !rest.is_empty() {
+        return Err(malformed("the end of the blueprint"));
+    }
+    Ok(Blueprint {
+        id: numbers[0],
+        costs: [
+            [numbers[1], 0, 0],
+            [numbers[2], 0, 0],
+            [numbers[3], numbers[4], 0],
+            [numbers[5], 0, numbers[6]],
+        ],
+    })
+}
+
+fn collect_blueprints(input: &str) -> Result<Vec<Blueprint>> {
+    numbered_lines(input)
+        .map(|(line_number, l)| parse_blueprint(line_number, l))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parts::Part;
+    use crate::test_helpers::read_fixture;
+
+    fn fixture_blueprints() -> Vec<Blueprint> {
+        collect_blueprints(&read_fixture(19, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_blueprints() {
+        let blueprints = fixture_blueprints();
+        assert_eq!(
+            blueprints[0],
+            Blueprint {
+                id: 1,
+                costs: [[4, 0, 0], [2, 0, 0], [3, 14, 0], [2, 0, 7]],
+            }
+        );
+        assert_eq!(blueprints[1].max_robots(), [3, 8, 12]);
+        let line = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs two ore.";
+        assert_eq!(
+            collect_blueprints(line),
+            Err(Error::parse(
+                1,
+                1,
+                line,
+                "expected `ore. Each obsidian robot costs`"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_max_geodes() {
+        let blueprints = fixture_blueprints();
+        assert_eq!(max_geodes(&blueprints[0], 24), 9);
+        assert_eq!(max_geodes(&blueprints[1], 24), 12);
+        assert_eq!(max_geodes(&blueprints[0], 32), 56);
+        assert_eq!(max_geodes(&blueprints[1], 32), 62);
+        assert_eq!(max_geodes(&blueprints[0], MAX_MINUTES), 158);
+    }
+
+    #[test]
+    fn test_params() {
+        let input = read_fixture(19, "test.txt");
+        let selector = |part: Part, name: &str, value: &str| {
+            Selector::new(part, Params::default().with(name, value))
+        };
+        assert_eq!(
+            run(&selector(Part::One, "minutes", "10"), &input),
+            Ok(Answer::from(0))
+        );
+        assert_eq!(
+            run(&selector(Part::One, "minutes", "100000"), &input),
+            Err(Error::Usage(String::from(
+                "Invalid value 100000 for --minutes"
+            )))
+        );
+        assert_eq!(
+            run(&selector(Part::One, "minutes", "41"), &input),
+            Err(Error::Usage(String::from("Invalid value 41 for --minutes")))
+        );
+        let first = selector(Part::Two, "blueprints", "1");
+        assert_eq!(run(&first, &input), Ok(Answer::from(56)));
+        let many = (0..13).map(|_| input.as_str()).collect::<String>();
+        assert_eq!(
+            run(&selector(Part::Two, "blueprints", "26"), &many),
+            Err(Error::Failed(String::from(
+                "the product of geodes does not fit in a u64"
+            )))
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+
+pub const DECRYPTION_KEY: i64 = 811_589_153;
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Grove Positioning System";
+
+    type Input<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_numbers(input)
+    }
+
+    fn part_one(numbers: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(grove_coordinates(&mix(numbers, 1))?))
+    }
+
+    fn part_two(numbers: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        let decrypted = decrypt(numbers)?;
+        Ok(Answer::from(grove_coordinates(&mix(&decrypted, 10))?))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day20::run(selector, input)
+}
+
+fn decrypt(numbers: &[i64]) -> Result<Vec<i64>> {
+    numbers
+        .iter()
+        .map(|number| {
+            number
+                .checked_mul(DECRYPTION_KEY)
+                .ok_or_else(|| Error::Failed(format!("{} cannot be decrypted as an i64", number)))
+        })
+        .collect()
+}
+
+// The file has repeated numbers, so the mixing order is tracked by each number's
+// index in the original file rather than by value. Moving a number past all the
+// others brings it back to where it started, so moves wrap around the other n - 1.
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    if numbers.len() < 2 {
+        return numbers.to_vec();
+    }
+    let mut order = (0..numbers.len()).collect::<Vec<usize>>();
+    let others = numbers.len() as i64 - 1;
+    for _ in 0..rounds {
+        for (index, number) in numbers.iter().enumerate() {
+            let position = order.iter().position(|&moving| moving == index).unwrap();
+            order.remove(position);
+            let position = (position as i64 + number.rem_euclid(others)).rem_euclid(others);
+            let position = position as usize;
+            order.insert(position, index);
+        }
+    }
+    order.iter().map(|&index| numbers[index]).collect()
+}
+
+/// The sum of the 1000th, 2000th and 3000th numbers after the 0, if there is one.
+fn grove_coordinates(mixed: &[i64]) -> Result<Option<i64>> {
+    let Some(zero) = mixed.iter().position(|&number| number == 0) else {
+        return Ok(None);
+    };
+    [1000, 2000, 3000]
+        .iter()
+        .try_fold(0i64, |sum, offset| {
+            sum.checked_add(mixed[(zero + offset) % mixed.len()])
+        })
+        .map(Some)
+        .ok_or_else(|| Error::Failed(String::from("the grove coordinates overflow an i64")))
+}
+
+fn collect_numbers(input: &str) -> Result<Vec<i64>> {
+    numbered_lines(input)
+        .map(|(line_number, l)| parse_field(line_number, l, l, "expected a number"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::parts::Part;
+    use crate::test_helpers::read_fixture;
+
+    fn fixture_numbers() -> Vec<i64> {
+        collect_numbers(&read_fixture(20, "test.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_collect_numbers() {
+        assert_eq!(fixture_numbers(), vec![1, 2, -3, 3, -2, 0, 4]);
+        assert_eq!(
+            collect_numbers("1\n-x\n"),
+            Err(Error::parse(2, 1, "-x", "expected a number"))
+        );
+    }
+
+    #[test]
+    fn test_mix() {
+        let numbers = fixture_numbers();
+        assert_eq!(mix(&numbers, 1), vec![-2, 1, 2, -3, 4, 0, 3]);
+        // Each of the repeated numbers moves once, rather than the first one twice.
+        assert_eq!(mix(&[3, 0, 3], 1), vec![0, 3, 3]);
+        assert_eq!(mix(&[0, i64::MAX, 1], 1), vec![i64::MAX, 1, 0]);
+    }
+
+    #[test]
+    fn test_grove_coordinates() {
+        let numbers = fixture_numbers();
+        assert_eq!(grove_coordinates(&mix(&numbers, 1)), Ok(Some(3)));
+        let decrypted = decrypt(&numbers).unwrap();
+        assert_eq!(
+            grove_coordinates(&mix(&decrypted, 10)),
+            Ok(Some(1623178306))
+        );
+        assert_eq!(grove_coordinates(&[1, 2]), Ok(None));
+        assert_eq!(
+            grove_coordinates(&[0, i64::MAX, 1]),
+            Err(Error::Failed(String::from(
+                "the grove coordinates overflow an i64"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_decrypt_reports_overflow() {
+        assert_eq!(
+            run(&Selector::from(Part::Two), "0\n20000000000\n1\n"),
+            Err(Error::Failed(String::from(
+                "20000000000 cannot be decrypted as an i64"
+            )))
+        );
+        assert!(run(&Selector::from(Part::One), "0\n20000000000\n1\n").is_ok());
+    }
+}
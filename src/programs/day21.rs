@@ -0,0 +1,405 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parsing::{column_of, numbered_lines, parse_field};
+use crate::parts::{Params, Selector};
+use crate::solver::Solver;
+use std::collections::{HashMap, HashSet};
+
+pub const ROOT: &str = "root";
+pub const HUMAN: &str = "humn";
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Monkey Math";
+
+    type Input<'a> = Monkeys<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        collect_monkeys(input)
+    }
+
+    fn part_one(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(monkeys.evaluate(ROOT)?))
+    }
+
+    fn part_two(monkeys: &Self::Input<'_>, _params: &Params) -> Result<Answer> {
+        Ok(Answer::from(monkeys.solve_for_human()?))
+    }
+}
+
+pub fn run(selector: &Selector, input: &str) -> Result<Answer> {
+    Day21::run(selector, input)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    /// The result, or `None` on overflow or a division that leaves a remainder.
+    fn apply(&self, left: i64, right: i64) -> Option<i64> {
+        match self {
+            Operator::Add => left.checked_add(right),
+            Operator::Subtract => left.checked_sub(right),
+            Operator::Multiply => left.checked_mul(right),
+            Operator::Divide => match left.checked_rem(right)? {
+                0 => left.checked_div(right),
+                _ => None,
+            },
+        }
+    }
+
+    /// The left operand that gives `result` with this right operand.
+    fn solve_left(&self, result: i64, right: i64) -> Option<i64> {
+        match self {
+            Operator::Add => Operator::Subtract.apply(result, right),
+            Operator::Subtract => Operator::Add.apply(result, right),
+            Operator::Multiply => Operator::Divide.apply(result, right),
+            Operator::Divide => Operator::Multiply.apply(result, right),
+        }
+    }
+
+    /// The right operand that gives `result` with this left operand.
+    fn solve_right(&self, left: i64, result: i64) -> Option<i64> {
+        match self {
+            Operator::Add => Operator::Subtract.apply(result, left),
+            Operator::Subtract => Operator::Subtract.apply(left, result),
+            Operator::Multiply => Operator::Divide.apply(result, left),
+            Operator::Divide => Operator::Divide.apply(left, result),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Job<'a> {
+    Number(i64),
+    Operation(&'a str, Operator, &'a str),
+}
+
+/// Each monkey's job by name. Every monkey a job refers to has a job of its own,
+/// and no monkey waits for itself, however indirectly.
+#[derive(Debug, PartialEq)]
+pub struct Monkeys<'a> {
+    jobs: HashMap<&'a str, Job<'a>>,
+    /// Every monkey, each after the monkeys it waits for.
+    order: Vec<&'a str>,
+}
+
+impl<'a> Monkeys<'a> {
+    /// What every monkey yells, or `None` where that is not a whole i64. Monkeys
+    /// heard by several others are worked out only once.
+    fn values(&self) -> HashMap<&'a str, Option<i64>> {
+        let mut values: HashMap<&str, Option<i64>> = HashMap::new();
+        for &name in &self.order {
+            let value = match self.jobs[name] {
+                Job::Number(number) => Some(number),
+                Job::Operation(left, operator, right) => match (values[left], values[right]) {
+                    (Some(left), Some(right)) => operator.apply(left, right),
+                    _ => None,
+                },
+            };
+            values.insert(name, value);
+        }
+        values
+    }
+
+    fn evaluate(&self, name: &str) -> Result<i64> {
+        value_of(&self.values(), name)
+    }
+
+    /// The monkeys whose number depends on what the human yells.
+    fn human_dependents(&self) -> HashSet<&'a str> {
+        let mut dependents: HashSet<&str> = HashSet::new();
+        for &name in &self.order {
+            let depends = match self.jobs[name] {
+                _ if name == HUMAN => true,
+                Job::Number(_) => false,
+                Job::Operation(left, _, right) => {
+                    dependents.contains(left) || dependents.contains(right)
+                }
+            };
+            if depends {
+                dependents.insert(name);
+            }
+        }
+        dependents
+    }
+
+    // The root's two operands must be equal. Only one side depends on the human,
+    // so the other side is the value the first must have; working down towards
+    // the human, each operation is inverted to find what its unknown operand must be.
+    fn solve_for_human(&self) -> Result<i64> {
+        let Job::Operation(left, _, right) = self.jobs[ROOT] else {
+            return Err(Error::Failed(format!(
+                "{} does not compare two monkeys",
+                ROOT
+            )));
+        };
+        let (values, dependents) = (self.values(), self.human_dependents());
+        let (mut name, mut target) = match dependents.contains(left) {
+            true => (left, value_of(&values, right)?),
+            false => (right, value_of(&values, left)?),
+        };
+        while name != HUMAN {
+            let Job::Operation(left, operator, right) = self.jobs[name] else {
+                return Err(Error::Failed(format!(
+                    "{} does not depend on {}",
+                    ROOT, HUMAN
+                )));
+            };
+            if dependents.contains(left) && dependents.contains(right) {
+                let message = format!("{} appears on both sides of {}", HUMAN, name);
+                return Err(Error::Failed(message));
+            }
+            let solved = match dependents.contains(left) {
+                true => (left, operator.solve_left(target, value_of(&values, right)?)),
+                false => (
+                    right,
+                    operator.solve_right(value_of(&values, left)?, target),
+                ),
+            };
+            target = solved.1.ok_or_else(|| {
+                Error::Failed(format!("no whole number for {} gives {}", solved.0, target))
+            })?;
+            name = solved.0;
+        }
+        Ok(target)
+    }
+}
+
+fn value_of(values: &HashMap<&str, Option<i64>>, name: &str) -> Result<i64> {
+    values[name]
+        .ok_or_else(|| Error::Failed(format!("{} cannot be worked out exactly as an i64", name)))
+}
+
+fn parse_job<'a>(line_number: usize, l: &'a str, job: &'a str) -> Result<Job<'a>> {
+    let fields = job.split_whitespace().collect::<Vec<&str>>();
+    match fields[..] {
+        [number] => Ok(Job::Number(parse_field(
+            line_number,
+            l,
+            number,
+            "expected a number",
+        )?)),
+        [left, operator, right] => {
+            let operator = match operator {
+                "+" => Operator::Add,
+                "-" => Operator::Subtract,
+                "*" => Operator::Multiply,
+                "/" => Operator::Divide,
+                _ => {
+                    let (column, message) = (column_of(l, operator), "expected +, -, * or /");
+                    return Err(Error::parse(line_number, column, operator, message));
+                }
+            };
+            Ok(Job::Operation(left, operator, right))
+        }
+        _ => {
+            let message = "expected a number or <monkey> <operator> <monkey>";
+            Err(Error::parse(line_number, column_of(l, job), job, message))
+        }
+    }
+}
+
+/// A monkey's name with the line its job is on.
+type NamedLine<'a> = (&'a str, usize, &'a str);
+
+fn collect_monkeys(input: &str) -> Result<Monkeys<'_>> {
+    let mut jobs: HashMap<&str, Job> = HashMap::new();
+    let mut named_lines: Vec<NamedLine> = vec![];
+    for (line_number, l) in numbered_lines(input) {
+        let (name, job) = l
+            .split_once(':')
+            .ok_or_else(|| Error::parse(line_number, 1, l, "expected `<monkey>: <job>`"))?;
+        let name = name.trim();
+        if jobs.insert(name, parse_job(line_number, l, job)?).is_some() {
+            let message = "expected each monkey only once";
+            return Err(Error::parse(line_number, column_of(l, name), name, message));
+        }
+        named_lines.push((name, line_number, l));
+    }
+    for &(name, line_number, l) in &named_lines {
+        if let Job::Operation(left, _, right) = jobs[name] {
+            let unknown = [left, right]
+                .into_iter()
+                .find(|other| !jobs.contains_key(other));
+            if let Some(unknown) = unknown {
+                let message = format!("expected a monkey with a job, not {}", unknown);
+                let column = column_of(l, unknown);
+                return Err(Error::parse(line_number, column, unknown, &message));
+            }
+        }
+    }
+    for required in [ROOT, HUMAN] {
+        if !jobs.contains_key(required) {
+            let (_, line_number, l) = named_lines.last().copied().unwrap_or(("", 0, ""));
+            let message = format!("expected a monkey named {}", required);
+            return Err(Error::parse(line_number, 1, l, &message));
+        }
+    }
+    let order = waiting_order(&jobs, &named_lines)?;
+    Ok(Monkeys { jobs, order })
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    Started,
+    Finished,
+}
+
+// A depth-first search from each monkey in turn, kept on an explicit stack so that
+// long chains of monkeys cannot overflow the call stack. Reaching a monkey whose
+// search has started but not finished means it waits for itself.
+fn waiting_order<'a>(
+    jobs: &HashMap<&'a str, Job<'a>>,
+    named_lines: &[NamedLine<'a>],
+) -> Result<Vec<&'a str>> {
+    let mut visits: HashMap<&str, Visit> = HashMap::new();
+    let mut order: Vec<&str> = vec![];
+    for &(first, _, _) in named_lines {
+        if visits.contains_key(first) {
+            continue;
+        }
+        visits.insert(first, Visit::Started);
+        let mut stack: Vec<(&str, usize)> = vec![(first, 0)];
+        while let Some(&(name, next)) = stack.last() {
+            let operands = match jobs[name] {
+                Job::Number(_) => vec![],
+                Job::Operation(left, _, right) => vec![left, right],
+            };
+            let Some(&operand) = operands.get(next) else {
+                visits.insert(name, Visit::Finished);
+                order.push(name);
+                stack.pop();
+                continue;
+            };
+            if let Some(top) = stack.last_mut() {
+                top.1 += 1;
+            }
+            match visits.get(operand) {
+                None => {
+                    visits.insert(operand, Visit::Started);
+                    stack.push((operand, 0));
+                }
+                Some(Visit::Started) => {
+                    let (_, line_number, l) = named_lines
+                        .iter()
+                        .copied()
+                        .find(|&(other, _, _)| other == name)
+                        .unwrap_or(("", 0, ""));
+                    let message = format!("expected a monkey that does not wait for {}", name);
+                    let column = column_of(l, operand);
+                    return Err(Error::parse(line_number, column, operand, &message));
+                }
+                Some(Visit::Finished) => {}
+            }
+        }
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::test_helpers::{read_fixture, TestCase};
+
+    #[test]
+    fn test_collect_monkeys() {
+        let input = read_fixture(21, "test.txt");
+        let monkeys = collect_monkeys(&input).unwrap();
+        assert_eq!(monkeys.jobs.len(), 15);
+        assert_eq!(
+            monkeys.jobs["root"],
+            Job::Operation("pppw", Operator::Add, "sjmn")
+        );
+        assert_eq!(monkeys.jobs["dbpl"], Job::Number(5));
+    }
+
+    #[test]
+    fn test_collect_monkeys_reports_bad_lines() {
+        let unknown = "expected a monkey with a job, not abcd";
+        let cases = [
+            (
+                "root: humn % humn\nhumn: 1\n",
+                Error::parse(1, 12, "%", "expected +, -, * or /"),
+            ),
+            (
+                "root: humn + abcd\nhumn: 1\n",
+                Error::parse(1, 14, "abcd", unknown),
+            ),
+            (
+                "root: 5\n",
+                Error::parse(1, 1, "root: 5", "expected a monkey named humn"),
+            ),
+            (
+                "root: humn + humn\nhumn: 1\nhumn: 2\n",
+                Error::parse(3, 1, "humn", "expected each monkey only once"),
+            ),
+            (
+                "root: humn + aaaa\nhumn: 1\naaaa: bbbb * humn\nbbbb: root - humn\n",
+                Error::parse(
+                    4,
+                    7,
+                    "root",
+                    "expected a monkey that does not wait for bbbb",
+                ),
+            ),
+            (
+                "root: humn + aaaa\nhumn: 1\naaaa: aaaa * humn\n",
+                Error::parse(
+                    3,
+                    7,
+                    "aaaa",
+                    "expected a monkey that does not wait for aaaa",
+                ),
+            ),
+        ];
+        cases.into_iter().for_each(|(input, error)| {
+            assert_eq!(collect_monkeys(input), Err(error));
+        });
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let input = read_fixture(21, "test.txt");
+        let monkeys = collect_monkeys(&input).unwrap();
+        let names = vec!["root", "drzm", "sjmn", "humn"];
+        let expecteds = vec![152, 30, 150, 5];
+        TestCase::create_many(names, expecteds)
+            .iter()
+            .for_each(|case| {
+                assert_eq!(monkeys.evaluate(case.input), Ok(case.expected));
+            });
+    }
+
+    #[test]
+    fn test_solve_for_human() {
+        let input = read_fixture(21, "test.txt");
+        assert_eq!(collect_monkeys(&input).unwrap().solve_for_human(), Ok(301));
+        let monkeys = collect_monkeys("root: humn + five\nhumn: 1\nfive: 5\n").unwrap();
+        assert_eq!(monkeys.solve_for_human(), Ok(5));
+        let input = "root: half + five\nhalf: humn / two\nhumn: 1\nfive: 5\ntwo: 2\n";
+        let monkeys = collect_monkeys(input).unwrap();
+        assert_eq!(monkeys.solve_for_human(), Ok(10));
+    }
+
+    #[test]
+    fn test_shared_monkeys_are_worked_out_once() {
+        // Each monkey hears the one before it twice, so without reusing values the
+        // work would double with every monkey.
+        let mut input = String::from("root: m100 + humn\nhumn: 1\nm0: 1\n");
+        for index in 1..=100 {
+            input.push_str(&format!("m{}: m{} / m{}\n", index, index - 1, index - 1));
+        }
+        let monkeys = collect_monkeys(&input).unwrap();
+        assert_eq!(monkeys.evaluate(ROOT), Ok(2));
+        assert_eq!(monkeys.solve_for_human(), Ok(1));
+    }
+}
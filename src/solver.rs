@@ -0,0 +1,170 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::parts::{OptionSpec, Params, Part, Selector};
+use crate::timing::timed;
+use std::io::BufRead;
+use std::time::Duration;
+
+/// A day's puzzle solution: parse the input once, then answer either part from it.
+pub trait Solver {
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// Whether `stream` works incrementally rather than reading the whole input first.
+    const STREAMING: bool = false;
+    /// Named variants beyond the two parts; each takes a value through the parameter of
+    /// the same name, e.g. `--top 5` selects the `top` variant with `top=5`.
+    const VARIANTS: &'static [OptionSpec] = &[];
+    /// Parameters that tune the canonical parts, e.g. the row day 15 inspects.
+    const PARAMS: &'static [OptionSpec] = &[];
+
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+    fn part_one(input: &Self::Input<'_>, params: &Params) -> Result<Answer>;
+    fn part_two(input: &Self::Input<'_>, params: &Params) -> Result<Answer>;
+
+    fn variant(_input: &Self::Input<'_>, name: &str, _params: &Params) -> Result<Answer> {
+        Err(Error::Usage(format!(
+            "Day {:02} has no variant {}",
+            Self::DAY,
+            name
+        )))
+    }
+
+    fn solve(parsed: &Self::Input<'_>, selector: &Selector) -> Result<Answer> {
+        match &selector.part {
+            Part::One => Self::part_one(parsed, &selector.params),
+            Part::Two => Self::part_two(parsed, &selector.params),
+            Part::Variant(name) => Self::variant(parsed, name, &selector.params),
+        }
+    }
+
+    fn run(selector: &Selector, input: &str) -> Result<Answer> {
+        let result = Self::parse(input).and_then(|parsed| Self::solve(&parsed, selector));
+        result.map_err(|error| error.for_day(Self::DAY))
+    }
+
+    /// Parses once and solves each requested part, timing every phase separately.
+    fn execute(selectors: &[Selector], input: &str) -> Result<Execution> {
+        let (parsed, parse_time) = timed(|| Self::parse(input));
+        let parsed = parsed.map_err(|error| error.for_day(Self::DAY))?;
+        let mut parts: Vec<PartExecution> = vec![];
+        for selector in selectors {
+            let (answer, elapsed) = timed(|| Self::solve(&parsed, selector));
+            parts.push(PartExecution {
+                selector: selector.clone(),
+                answer: answer.map_err(|error| error.for_day(Self::DAY))?,
+                elapsed,
+            });
+        }
+        Ok(Execution { parse_time, parts })
+    }
+
+    /// Solves each requested part straight from a reader. Days that are naturally
+    /// streaming override this to avoid holding the whole input in memory.
+    fn stream(reader: &mut dyn BufRead, selectors: &[Selector]) -> Result<Vec<Answer>> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|error| Error::io("input", error))?;
+        let parsed = Self::parse(&input)?;
+        selectors
+            .iter()
+            .map(|selector| Self::solve(&parsed, selector))
+            .collect()
+    }
+}
+
+pub struct Execution {
+    pub parse_time: Duration,
+    pub parts: Vec<PartExecution>,
+}
+
+pub struct PartExecution {
+    pub selector: Selector,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// A registered day, with its solver erased so days can be listed and run uniformly.
+#[derive(Clone, Copy)]
+pub struct Program {
+    pub day: u8,
+    pub title: &'static str,
+    pub streaming: bool,
+    pub variants: &'static [OptionSpec],
+    pub params: &'static [OptionSpec],
+    run: fn(&Selector, &str) -> Result<Answer>,
+    execute: fn(&[Selector], &str) -> Result<Execution>,
+    stream: fn(&mut dyn BufRead, &[Selector]) -> Result<Vec<Answer>>,
+}
+
+impl Program {
+    pub const fn of<S: Solver>() -> Self {
+        Program {
+            day: S::DAY,
+            title: S::TITLE,
+            streaming: S::STREAMING,
+            variants: S::VARIANTS,
+            params: S::PARAMS,
+            run: S::run,
+            execute: S::execute,
+            stream: S::stream,
+        }
+    }
+
+    pub fn data_dir(&self) -> String {
+        format!("data/day{:02}", self.day)
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("{}/input.txt", self.data_dir())
+    }
+
+    pub fn run(&self, selector: &Selector, input: &str) -> Result<Answer> {
+        (self.run)(selector, input)
+    }
+
+    pub fn execute(&self, selectors: &[Selector], input: &str) -> Result<Execution> {
+        (self.execute)(selectors, input)
+    }
+
+    pub fn stream(&self, reader: &mut dyn BufRead, selectors: &[Selector]) -> Result<Vec<Answer>> {
+        (self.stream)(reader, selectors).map_err(|error| error.for_day(self.day))
+    }
+
+    /// Turns the parts and `--name value` options given on the command line into
+    /// selectors, checking them against what this day accepts. Without explicit parts,
+    /// naming a variant's option selects that variant; otherwise both parts are solved.
+    pub fn selectors(&self, parts: Option<Vec<Part>>, params: Params) -> Result<Vec<Selector>> {
+        let is_variant = |name: &str| self.variants.iter().any(|spec| spec.name == name);
+        if let Some(name) = params
+            .names()
+            .find(|&name| !is_variant(name) && !self.params.iter().any(|spec| spec.name == name))
+        {
+            return Err(Error::Usage(format!(
+                "Day {:02} has no option --{}",
+                self.day, name
+            )));
+        }
+        let parts = match parts {
+            Some(parts) => parts,
+            None if params.names().any(is_variant) => params
+                .names()
+                .filter(|&name| is_variant(name))
+                .map(Part::from_label)
+                .collect(),
+            None => vec![Part::One, Part::Two],
+        };
+        parts
+            .into_iter()
+            .map(|part| match &part {
+                Part::Variant(name) if !is_variant(name) => Err(Error::Usage(format!(
+                    "Day {:02} has no part {}",
+                    self.day, name
+                ))),
+                _ => Ok(Selector::new(part, params.clone())),
+            })
+            .collect()
+    }
+}
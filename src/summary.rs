@@ -0,0 +1,106 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::timing::format_duration;
+use std::time::Duration;
+
+/// One line of the `all` summary: a day's part and how it went.
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+pub enum Outcome {
+    Solved(Answer),
+    Failed(Error),
+    MissingInput(String),
+}
+
+impl Outcome {
+    fn lines(&self) -> Vec<String> {
+        match self {
+            Outcome::Solved(answer) => answer.to_string().lines().map(String::from).collect(),
+            Outcome::Failed(error) => vec![format!("error: {}", error)],
+            Outcome::MissingInput(path) => vec![format!("missing input {}", path)],
+        }
+    }
+}
+
+/// Renders the rows as an aligned table with an overall total; multi-line answers
+/// (such as grids) continue on the following lines of the answer column.
+pub fn render_table(rows: &[Row]) -> String {
+    let answer_width = rows
+        .iter()
+        .flat_map(|row| row.outcome.lines())
+        .map(|line| line.chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!("Day  Part  {:<answer_width$}  Time\n", "Answer");
+    for row in rows {
+        let time = match row.outcome {
+            Outcome::MissingInput(_) => String::from("-"),
+            _ => format_duration(row.elapsed),
+        };
+        for (index, line) in row.outcome.lines().iter().enumerate() {
+            if index == 0 {
+                table.push_str(&format!(
+                    "{:02}   {:<4}  {:<answer_width$}  {}\n",
+                    row.day, row.part, line, time
+                ));
+            } else {
+                table.push_str(&format!("           {}\n", line));
+            }
+        }
+    }
+    let total = rows.iter().map(|row| row.elapsed).sum::<Duration>();
+    table.push_str(&format!(
+        "Total      {:<answer_width$}  {}\n",
+        "",
+        format_duration(total)
+    ));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            Row {
+                day: 1,
+                part: 1,
+                outcome: Outcome::Solved(Answer::Integer(24000)),
+                elapsed: Duration::from_micros(20),
+            },
+            Row {
+                day: 10,
+                part: 2,
+                outcome: Outcome::Solved(Answer::Grid(vec![
+                    String::from("#.#"),
+                    String::from(".#."),
+                ])),
+                elapsed: Duration::from_micros(30),
+            },
+            Row {
+                day: 11,
+                part: 1,
+                outcome: Outcome::MissingInput(String::from("data/day11/input.txt")),
+                elapsed: Duration::ZERO,
+            },
+        ];
+        let expected = "\
+Day  Part  Answer                              Time
+01   1     24000                               20.0µs
+10   2     #.#                                 30.0µs
+           .#.
+11   1     missing input data/day11/input.txt  -
+Total                                          50.0µs
+";
+        assert_eq!(render_table(&rows), expected);
+    }
+}
@@ -1,4 +1,6 @@
-pub fn vec_compare<T: PartialEq>(a: &Vec<T>, b: &Vec<T>) -> bool {
+use std::fs;
+
+pub fn vec_compare<T: PartialEq>(a: &[T], b: &[T]) -> bool {
     let match_count = a.iter().zip(b.iter()).filter(|&(a, b)| a == b).count();
     match_count == a.len() && match_count == b.len()
 }
@@ -22,3 +24,9 @@ impl<TInput: Clone, TExpected: Clone> TestCase<TInput, TExpected> {
             .collect::<Vec<TestCase<TInput, TExpected>>>()
     }
 }
+
+/// Reads `name` from a day's data directory, such as its example `test.txt`.
+pub fn read_fixture(day: u8, name: &str) -> String {
+    let path = format!("./data/day{:02}/{}", day, name);
+    fs::read_to_string(path).expect("Failed to read input file")
+}
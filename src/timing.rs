@@ -0,0 +1,158 @@
+use crate::error::{Error, Result};
+use crate::parts::{Part, Selector};
+use crate::solver::Program;
+use std::time::{Duration, Instant};
+
+/// Runs `f`, returning its result together with how long it took.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Formats a duration with a unit suited to its size, e.g. `850ns`, `12.3µs`, `4.56ms` or `1.20s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() {
+            0 => return None,
+            n if n % 2 == 0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+        Some(Stats {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+pub struct Benchmark {
+    pub day: u8,
+    pub runs: usize,
+    pub parse: Stats,
+    pub parts: Vec<(Part, Stats)>,
+}
+
+/// Parses and solves both parts `runs` times, collecting timings for each phase.
+pub fn bench(program: &Program, input: &str, runs: usize) -> Result<Benchmark> {
+    let selectors = [Selector::from(Part::One), Selector::from(Part::Two)];
+    let mut parse_samples: Vec<Duration> = vec![];
+    let mut part_samples: Vec<Vec<Duration>> = vec![vec![]; selectors.len()];
+    for _ in 0..runs {
+        let execution = program.execute(&selectors, input)?;
+        parse_samples.push(execution.parse_time);
+        for (samples, part) in part_samples.iter_mut().zip(&execution.parts) {
+            samples.push(part.elapsed);
+        }
+    }
+    let no_runs = || Error::Usage(String::from("Benchmarks need at least one run"));
+    Ok(Benchmark {
+        day: program.day,
+        runs,
+        parse: Stats::of(&parse_samples).ok_or_else(no_runs)?,
+        parts: selectors
+            .into_iter()
+            .zip(&part_samples)
+            .map(|(selector, samples)| Ok((selector.part, Stats::of(samples).ok_or_else(no_runs)?)))
+            .collect::<Result<Vec<(Part, Stats)>>>()?,
+    })
+}
+
+pub fn render_benchmarks(benchmarks: &[Benchmark]) -> String {
+    let mut table = format!(
+        "{:<4} {:<7} {:>5} {:>10} {:>10} {:>10}\n",
+        "Day", "Phase", "Runs", "Min", "Median", "Max"
+    );
+    for benchmark in benchmarks {
+        let phases = [(String::from("parse"), benchmark.parse)]
+            .into_iter()
+            .chain(
+                benchmark
+                    .parts
+                    .iter()
+                    .map(|(part, stats)| (format!("part {}", part), *stats)),
+            );
+        for (phase, stats) in phases {
+            table.push_str(&format!(
+                "{:<4} {:<7} {:>5} {:>10} {:>10} {:>10}\n",
+                format!("{:02}", benchmark.day),
+                phase,
+                benchmark.runs,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max)
+            ));
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestCase;
+
+    #[test]
+    fn test_format_duration() {
+        let inputs = vec![
+            Duration::from_nanos(850),
+            Duration::from_nanos(12_345),
+            Duration::from_micros(4_560),
+            Duration::from_millis(1_200),
+        ];
+        let expecteds = vec!["850ns", "12.3µs", "4.56ms", "1.20s"];
+        TestCase::create_many(inputs, expecteds)
+            .iter()
+            .for_each(|case| assert_eq!(format_duration(case.input), case.expected));
+    }
+
+    #[test]
+    fn test_stats_of() {
+        let samples = [5, 1, 3, 9].map(Duration::from_micros);
+        let expected = Stats {
+            min: Duration::from_micros(1),
+            median: Duration::from_micros(4),
+            max: Duration::from_micros(9),
+        };
+        assert_eq!(Stats::of(&samples), Some(expected));
+        assert_eq!(
+            Stats::of(&samples[..3]).map(|s| s.median),
+            Some(Duration::from_micros(3))
+        );
+        assert_eq!(Stats::of(&[]), None);
+    }
+
+    #[test]
+    fn test_bench() {
+        let program = crate::programs::find_program(1).unwrap();
+        let benchmark = bench(program, "1000\n2000\n\n4000\n", 3).unwrap();
+        assert_eq!(benchmark.day, 1);
+        assert_eq!(benchmark.runs, 3);
+        assert_eq!(benchmark.parts.len(), 2);
+        assert!(benchmark.parse.min <= benchmark.parse.median);
+        assert!(benchmark.parse.median <= benchmark.parse.max);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::error::{Error, Result};
+use crate::parsing::numbered_lines;
+use crate::parts::{Params, Part, Selector};
+use crate::solver::Program;
+use std::fs;
+
+pub const MANIFEST_FILE: &str = "answers.txt";
+
+/// A recorded answer from a day's `answers.txt`, written as
+/// `<input file> <part> [<name>=<value>...]: <answer>`; multi-line answers follow
+/// on indented lines.
+#[derive(Debug, PartialEq)]
+pub struct Expected {
+    pub file: String,
+    pub selector: Selector,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing(String),
+    Error(Error),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub file: String,
+    pub selector: Selector,
+    pub status: Status,
+}
+
+pub fn parse_manifest(manifest: &str) -> Result<Vec<Expected>> {
+    let mut expecteds: Vec<Expected> = vec![];
+    for (line_number, l) in numbered_lines(manifest) {
+        if l.starts_with('#') {
+            continue;
+        }
+        // Indented lines continue the previous answer, one row each, so answers
+        // such as rendered images may themselves start with `#`.
+        if l.starts_with(char::is_whitespace) {
+            let expected = expecteds.last_mut().ok_or_else(|| {
+                Error::parse(
+                    line_number,
+                    1,
+                    l,
+                    "expected an answer before its continuation",
+                )
+            })?;
+            if !expected.answer.is_empty() {
+                expected.answer.push('\n');
+            }
+            expected.answer.push_str(l.trim_start());
+            continue;
+        }
+        expecteds.push(parse_expected(line_number, l)?);
+    }
+    Ok(expecteds)
+}
+
+fn parse_expected(line_number: usize, l: &str) -> Result<Expected> {
+    let malformed = || Error::parse(line_number, 1, l, "expected `<file> <part>: <answer>`");
+    let (key, answer) = l.split_once(':').ok_or_else(malformed)?;
+    let mut words = key.split_whitespace();
+    let (file, part) = match (words.next(), words.next()) {
+        (Some(file), Some(part)) => (file, Part::from_label(part)),
+        _ => return Err(malformed()),
+    };
+    if matches!(&part, Part::Variant(name) if name.parse::<u32>().is_ok()) {
+        return Err(malformed());
+    }
+    let mut params = Params::default();
+    for word in words {
+        let (name, value) = word.split_once('=').ok_or_else(malformed)?;
+        params.set(name, value);
+    }
+    Ok(Expected {
+        file: String::from(file),
+        selector: Selector::new(part, params),
+        answer: String::from(answer.trim()),
+    })
+}
+
+/// Checks every recorded answer for a day, and reports parts of its puzzle input
+/// that have no recorded answer as missing.
+pub fn verify_program(program: &Program) -> Vec<Check> {
+    let data_dir = program.data_dir();
+    let manifest_path = format!("{}/{}", data_dir, MANIFEST_FILE);
+    let expecteds = match fs::read_to_string(&manifest_path) {
+        Ok(manifest) => match parse_manifest(&manifest) {
+            Ok(expecteds) => expecteds,
+            Err(error) => {
+                return vec![Check {
+                    day: program.day,
+                    file: String::from(MANIFEST_FILE),
+                    selector: Selector::from(Part::One),
+                    status: Status::Error(error.for_day(program.day)),
+                }]
+            }
+        },
+        Err(_) => vec![],
+    };
+
+    let mut checks = expecteds
+        .iter()
+        .map(|expected| Check {
+            day: program.day,
+            file: expected.file.clone(),
+            selector: expected.selector.clone(),
+            status: check_answer(
+                program,
+                &format!("{}/{}", data_dir, expected.file),
+                expected,
+            ),
+        })
+        .collect::<Vec<Check>>();
+
+    for part in [Part::One, Part::Two] {
+        let recorded = expecteds
+            .iter()
+            .any(|expected| expected.file == "input.txt" && expected.selector.part == part);
+        if !recorded {
+            checks.push(Check {
+                day: program.day,
+                file: String::from("input.txt"),
+                selector: Selector::from(part),
+                status: Status::Missing(format!("no answer recorded in {}", manifest_path)),
+            });
+        }
+    }
+    checks
+}
+
+fn check_answer(program: &Program, path: &str, expected: &Expected) -> Status {
+    let input = match fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(_) => return Status::Missing(format!("no input file {}", path)),
+    };
+    match program.run(&expected.selector, &input) {
+        Ok(answer) if answer.to_string() == expected.answer => Status::Pass,
+        Ok(answer) => Status::Fail {
+            expected: expected.answer.clone(),
+            actual: answer.to_string(),
+        },
+        Err(error) => Status::Error(error),
+    }
+}
+
+pub fn render_checks(checks: &[Check]) -> String {
+    let parts = checks
+        .iter()
+        .map(|check| check.selector.to_string())
+        .collect::<Vec<String>>();
+    let width = parts
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or_default()
+        .max(4);
+    let file_width = checks
+        .iter()
+        .map(|check| check.file.len())
+        .max()
+        .unwrap_or_default()
+        .max(10);
+    let mut report = format!(
+        "Day  {:<file_width$}  {:<width$}  Status\n",
+        "Input", "Part"
+    );
+    for (check, part) in checks.iter().zip(&parts) {
+        let status = match &check.status {
+            Status::Pass => String::from("pass"),
+            Status::Fail { expected, actual } => {
+                format!("FAIL expected {} but got {}", expected, actual)
+            }
+            Status::Missing(reason) => format!("missing ({})", reason),
+            Status::Error(error) => format!("ERROR {}", error),
+        };
+        report.push_str(&format!(
+            "{:02}   {:<file_width$}  {:<width$}  {}\n",
+            check.day, check.file, part, status
+        ));
+    }
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|check| f(&check.status)).count();
+    report.push_str(&format!(
+        "{} passed, {} failed, {} missing\n",
+        count(|status| *status == Status::Pass),
+        count(|status| matches!(status, Status::Fail { .. } | Status::Error(_))),
+        count(|status| matches!(status, Status::Missing(_)))
+    ));
+    report
+}
+
+/// Panics with the report if any recorded answer no longer matches; used by the
+/// tests that build.rs generates for each registered day.
+pub fn assert_verified(program: &Program) {
+    let checks = verify_program(program);
+    let failed = checks
+        .iter()
+        .any(|check| matches!(check.status, Status::Fail { .. } | Status::Error(_)));
+    assert!(!failed, "\n{}", render_checks(&checks));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = "\
+# file part: answer
+input.txt 1: 68467
+
+test.txt 2: CMZ
+test.txt top top=2: 45000
+";
+        let expected = vec![
+            Expected {
+                file: String::from("input.txt"),
+                selector: Selector::from(Part::One),
+                answer: String::from("68467"),
+            },
+            Expected {
+                file: String::from("test.txt"),
+                selector: Selector::from(Part::Two),
+                answer: String::from("CMZ"),
+            },
+            Expected {
+                file: String::from("test.txt"),
+                selector: Selector::new(
+                    Part::Variant(String::from("top")),
+                    Params::default().with("top", "2"),
+                ),
+                answer: String::from("45000"),
+            },
+        ];
+        assert_eq!(parse_manifest(manifest), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_manifest_multi_line_answer() {
+        let manifest = "test.txt 2:\n  #..#\n  .##.\ntest.txt 1: 13140\n";
+        let expecteds = parse_manifest(manifest).unwrap();
+        assert_eq!(expecteds[0].answer, "#..#\n.##.");
+        assert_eq!(expecteds[1].answer, "13140");
+        // Only unindented lines are comments.
+        let manifest = "# rows:\ntest.txt 2:\n  ##..\n  # .#\n";
+        assert_eq!(parse_manifest(manifest).unwrap()[0].answer, "##..\n# .#");
+        assert_eq!(
+            parse_manifest("  #..#\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "  #..#",
+                "expected an answer before its continuation"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_manifest_rejects_bad_part() {
+        assert_eq!(
+            parse_manifest("input.txt 3: 1\n"),
+            Err(Error::parse(
+                1,
+                1,
+                "input.txt 3: 1",
+                "expected `<file> <part>: <answer>`"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_checks() {
+        let checks = vec![
+            Check {
+                day: 1,
+                file: String::from("input.txt"),
+                selector: Selector::from(Part::One),
+                status: Status::Pass,
+            },
+            Check {
+                day: 1,
+                file: String::from("input.txt"),
+                selector: Selector::from(Part::Two),
+                status: Status::Fail {
+                    expected: String::from("45000"),
+                    actual: String::from("44999"),
+                },
+            },
+        ];
+        let expected = "\
+Day  Input       Part  Status
+01   input.txt   1     pass
+01   input.txt   2     FAIL expected 45000 but got 44999
+1 passed, 1 failed, 0 missing
+";
+        assert_eq!(render_checks(&checks), expected);
+    }
+}